@@ -14,6 +14,8 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Initialize a constraints workspace in the current directory
+    Init(InitArgs),
     /// Add a new constraint
     Add(AddArgs),
     /// List constraints
@@ -22,10 +24,70 @@ pub enum Commands {
     Search(SearchArgs),
     /// Update an existing constraint
     Patch(PatchArgs),
-    /// Delete a constraint
+    /// Move a constraint to another category or change its type (the ID is kept)
+    Move(MoveArgs),
+    /// List aliases that redirect retired IDs
+    Aliases(AliasesArgs),
+    /// Re-derive constraint IDs with the current ID algorithm, keeping old IDs as aliases
+    Reid(ReidArgs),
+    /// Archive a constraint (or remove it permanently with --purge)
     Delete(DeleteArgs),
+    /// Restore an archived constraint
+    Restore(RestoreArgs),
+    /// Put a draft constraint up for review
+    Propose(TransitionArgs),
+    /// Accept a proposed (or reinstate a deprecated) constraint so it is enforced
+    Accept(TransitionArgs),
+    /// Send a proposed constraint back to draft
+    Withdraw(TransitionArgs),
+    /// Deprecate an accepted constraint
+    Deprecate(TransitionArgs),
+    /// Mark a constraint as superseded by another one
+    Supersede(SupersedeArgs),
+    /// Sign off on the current content of a constraint
+    Approve(ApproveArgs),
+    /// Sign constraint records with the local key (generating one if asked)
+    Sign(SignArgs),
+    /// Check every constraint record's signature against the trusted keys
+    VerifySignatures(VerifySignaturesArgs),
+    /// Show the revision history of a constraint
+    History(HistoryArgs),
+    /// Show the relationships of a constraint in both directions
+    Links(LinksArgs),
+    /// Export the constraint graph as Graphviz DOT or Mermaid
+    Graph(GraphArgs),
     /// Validate constraint compliance
     Validate(ValidateArgs),
+    /// Show a recorded validation run or compare it with an earlier one
+    Report(ReportArgs),
+    /// Manage the storage backend
+    Storage(StorageArgs),
+    /// Inspect the tamper-evident audit log
+    Audit(AuditArgs),
+}
+
+/// Arguments for initializing a workspace
+#[derive(Parser)]
+pub struct InitArgs {
+    /// Default author for new constraints
+    #[arg(short = 'A', long)]
+    pub author: Option<String>,
+
+    /// Comma-separated list of allowed categories (any category if omitted)
+    #[arg(short = 'c', long, value_delimiter = ',')]
+    pub categories: Vec<String>,
+
+    /// Prefix for generated constraint IDs
+    #[arg(short = 'p', long, default_value = "nt")]
+    pub id_prefix: String,
+
+    /// Default output format (human, json)
+    #[arg(short = 'o', long, default_value = "human")]
+    pub format: String,
+
+    /// Overwrite an existing workspace configuration
+    #[arg(short = 'f', long)]
+    pub force: bool,
 }
 
 /// Arguments for adding a constraint
@@ -43,9 +105,9 @@ pub struct AddArgs {
     #[arg(short = 'x', long)]
     pub text: String,
 
-    /// Author of the constraint
+    /// Author of the constraint (defaults to the workspace default author)
     #[arg(short = 'A', long)]
-    pub author: String,
+    pub author: Option<String>,
 
     /// Explicit constraint ID (optional, auto-generated if not provided)
     #[arg(short = 'i', long)]
@@ -63,9 +125,29 @@ pub struct AddArgs {
     #[arg(short = 'R', long)]
     pub references: Option<String>,
 
-    /// Verification command/script
+    /// Verification shell command/script
     #[arg(short = 'V', long)]
     pub verification: Option<String>,
+
+    /// Structured verification spec as JSON, e.g. '{"argv":["cargo","test"],"cwd":"api"}'
+    #[arg(long, conflicts_with = "verification")]
+    pub verification_spec: Option<String>,
+
+    /// IDs of constraints this one depends on (comma-separated, prefixes allowed)
+    #[arg(long, value_delimiter = ',')]
+    pub depends_on: Vec<String>,
+
+    /// IDs of constraints this one supersedes (comma-separated, prefixes allowed)
+    #[arg(long, value_delimiter = ',')]
+    pub supersedes: Vec<String>,
+
+    /// IDs of constraints this one conflicts with (comma-separated, prefixes allowed)
+    #[arg(long, value_delimiter = ',')]
+    pub conflicts_with: Vec<String>,
+
+    /// Lifecycle state to start in (draft, proposed, accepted)
+    #[arg(short = 'L', long, default_value = "accepted")]
+    pub lifecycle: String,
 }
 
 /// Arguments for listing constraints
@@ -75,9 +157,13 @@ pub struct ListArgs {
     #[arg(short = 'C', long)]
     pub category: Option<String>,
 
-    /// Output format (human, json)
-    #[arg(short = 'o', long, default_value = "human")]
-    pub format: String,
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+
+    /// Include archived constraints
+    #[arg(long)]
+    pub include_archived: bool,
 }
 
 /// Arguments for searching constraints
@@ -90,15 +176,19 @@ pub struct SearchArgs {
     #[arg(short = 'C', long)]
     pub category: Option<String>,
 
-    /// Output format (human, json)
-    #[arg(short = 'o', long, default_value = "human")]
-    pub format: String,
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+
+    /// Include archived constraints
+    #[arg(long)]
+    pub include_archived: bool,
 }
 
 /// Arguments for patching a constraint
 #[derive(Parser)]
 pub struct PatchArgs {
-    /// Constraint ID to update
+    /// Constraint ID to update (or a unique prefix of it)
     pub id: String,
 
     /// Updated constraint text
@@ -117,16 +207,196 @@ pub struct PatchArgs {
     #[arg(short = 'R', long)]
     pub references: Option<String>,
 
-    /// Updated verification command
+    /// Updated verification shell command
     #[arg(short = 'V', long)]
     pub verification: Option<String>,
+
+    /// Updated structured verification spec as JSON
+    #[arg(long, conflicts_with = "verification")]
+    pub verification_spec: Option<String>,
+
+    /// Replace the depends-on targets (comma-separated; pass '' to clear)
+    #[arg(long, value_delimiter = ',')]
+    pub depends_on: Option<Vec<String>>,
+
+    /// Replace the supersedes targets (comma-separated; pass '' to clear)
+    #[arg(long, value_delimiter = ',')]
+    pub supersedes: Option<Vec<String>>,
+
+    /// Replace the conflicts-with targets (comma-separated; pass '' to clear)
+    #[arg(long, value_delimiter = ',')]
+    pub conflicts_with: Option<Vec<String>>,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for moving or retyping a constraint
+#[derive(Parser)]
+pub struct MoveArgs {
+    /// Constraint ID to move (or a unique prefix of it)
+    pub id: String,
+
+    /// New category
+    #[arg(short = 'c', long)]
+    pub category: Option<String>,
+
+    /// New constraint type (MUST, SHALL, SHOULD, MAY, FORBIDDEN)
+    #[arg(short = 'T', long)]
+    pub r#type: Option<String>,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for listing ID aliases
+#[derive(Parser)]
+pub struct AliasesArgs {
+    /// Only show aliases whose target no longer exists
+    #[arg(long)]
+    pub dangling: bool,
+
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
 }
 
 /// Arguments for deleting a constraint
 #[derive(Parser)]
 pub struct DeleteArgs {
-    /// Constraint ID to delete
+    /// Constraint ID to delete (or a unique prefix of it)
     pub id: String,
+
+    /// Remove the constraint permanently instead of archiving it
+    #[arg(long)]
+    pub purge: bool,
+
+    /// Delete even if other constraints depend on this one
+    #[arg(short = 'f', long)]
+    pub force: bool,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for restoring an archived constraint
+#[derive(Parser)]
+pub struct RestoreArgs {
+    /// Constraint ID to restore (or a unique prefix of it)
+    pub id: String,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for re-identifying constraints
+#[derive(Parser)]
+pub struct ReidArgs {
+    /// Show which IDs would change without rewriting anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for moving a constraint to another lifecycle state
+#[derive(Parser)]
+pub struct TransitionArgs {
+    /// Constraint ID (or a unique prefix of it)
+    pub id: String,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for superseding a constraint
+#[derive(Parser)]
+pub struct SupersedeArgs {
+    /// ID of the constraint being replaced (or a unique prefix of it)
+    pub id: String,
+
+    /// ID of the replacing constraint (or a unique prefix of it)
+    #[arg(short = 'b', long)]
+    pub by: String,
+
+    /// Who is making the change (defaults to the workspace default author, then $USER)
+    #[arg(long)]
+    pub actor: Option<String>,
+}
+
+/// Arguments for approving a constraint
+#[derive(Parser)]
+pub struct ApproveArgs {
+    /// Constraint ID (or a unique prefix of it)
+    pub id: String,
+
+    /// Reviewer signing off (must not be the constraint's author)
+    #[arg(short = 'b', long)]
+    pub by: String,
+}
+
+/// Arguments for signing constraint records
+#[derive(Parser)]
+pub struct SignArgs {
+    /// Constraint IDs or unique prefixes (all constraints if omitted)
+    pub ids: Vec<String>,
+
+    /// Generate a local signing key first and trust its public key
+    #[arg(long)]
+    pub generate_key: bool,
+}
+
+/// Arguments for verifying constraint signatures
+#[derive(Parser)]
+pub struct VerifySignaturesArgs {
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+}
+
+/// Arguments for showing a constraint's revision history
+#[derive(Parser)]
+pub struct HistoryArgs {
+    /// Constraint ID (or a unique prefix of it)
+    pub id: String,
+
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+}
+
+/// Arguments for showing a constraint's relationships
+#[derive(Parser)]
+pub struct LinksArgs {
+    /// Constraint ID (or a unique prefix of it)
+    pub id: String,
+
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+}
+
+/// Arguments for exporting the constraint graph
+#[derive(Parser)]
+pub struct GraphArgs {
+    /// Filter by category
+    #[arg(short = 'C', long)]
+    pub category: Option<String>,
+
+    /// Include archived constraints
+    #[arg(long)]
+    pub include_archived: bool,
+
+    /// Graph format (dot, mermaid)
+    #[arg(short = 'o', long, default_value = "dot")]
+    pub format: String,
 }
 
 /// Arguments for validating constraints
@@ -136,7 +406,7 @@ pub struct ValidateArgs {
     #[arg(short, long)]
     pub category: Option<String>,
 
-    /// Validate specific constraint ID
+    /// Validate specific constraint ID (or a unique prefix of it)
     #[arg(short = 'I', long)]
     pub id: Option<String>,
 
@@ -147,4 +417,101 @@ pub struct ValidateArgs {
     /// Verbose output with full verification details
     #[arg(short = 'v', long)]
     pub verbose: bool,
+
+    /// Include archived constraints
+    #[arg(long)]
+    pub include_archived: bool,
+
+    /// Seconds each verification may run unless its spec sets a limit
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Verifications to run at once (defaults to the number of CPUs)
+    #[arg(short = 'j', long, value_name = "N")]
+    pub jobs: Option<std::num::NonZeroUsize>,
+
+    /// Run every verification even when a cached result for unchanged inputs exists
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Output format (human, json, junit; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+
+    /// Also write a JUnit XML report to this file
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<std::path::PathBuf>,
+}
+
+/// Arguments for showing recorded validation runs
+#[derive(Parser)]
+pub struct ReportArgs {
+    /// Run ID (or a unique prefix of it); defaults to the latest run
+    pub run: Option<String>,
+
+    /// List only constraints whose status changed since BASE (defaults to the run before)
+    #[arg(short = 'c', long, value_name = "BASE")]
+    pub compare: Option<Option<String>>,
+
+    /// List recorded runs instead of showing one
+    #[arg(short = 'l', long, conflicts_with_all = ["run", "compare"])]
+    pub list: bool,
+
+    /// Show the captured output of each check
+    #[arg(short = 'v', long)]
+    pub verbose: bool,
+
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
+}
+
+/// Arguments for storage management
+#[derive(Parser)]
+pub struct StorageArgs {
+    #[command(subcommand)]
+    pub command: StorageCommands,
+}
+
+#[derive(Subcommand)]
+pub enum StorageCommands {
+    /// Copy all constraints into another backend or layout and switch the workspace to it
+    Convert(ConvertArgs),
+}
+
+/// Arguments for converting between storage backends
+#[derive(Parser)]
+pub struct ConvertArgs {
+    /// Target backend (jsonl, sqlite)
+    #[arg(short = 't', long)]
+    pub to: String,
+
+    /// Target layout for the jsonl backend (per-constraint, per-category; defaults to current)
+    #[arg(short = 'l', long)]
+    pub layout: Option<String>,
+
+    /// Replace any constraints already present in the target backend
+    #[arg(short = 'f', long)]
+    pub force: bool,
+}
+
+/// Arguments for audit log commands
+#[derive(Parser)]
+pub struct AuditArgs {
+    #[command(subcommand)]
+    pub command: AuditCommands,
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// Walk the hash chain and report the first broken link
+    Verify(AuditVerifyArgs),
+}
+
+/// Arguments for verifying the audit log
+#[derive(Parser)]
+pub struct AuditVerifyArgs {
+    /// Output format (human, json; defaults to the workspace setting)
+    #[arg(short = 'o', long)]
+    pub format: Option<String>,
 }
@@ -3,61 +3,158 @@
 use crate::cli::args::AddArgs;
 use crate::core::constraint::{Constraint, ConstraintType};
 use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::core::lifecycle::Lifecycle;
+use crate::core::verification::VerificationSpec;
+use crate::storage::{self, check_relations, id_in_use, resolve_relation_targets, ConstraintStore};
+use crate::utils::config::WorkspaceConfig;
 use crate::utils::workspace::Workspace;
 
 /// Run the add command
 pub fn run(args: AddArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace (created explicitly by `constraint init`)
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let constraint = add_constraint(store.as_ref(), workspace.config(), &args)?;
+
+    // Output result
+    if args.id.is_none() {
+        println!("Constraint added with ID: {}", constraint.id);
+    } else {
+        println!("Constraint added: {}", constraint.id);
+    }
+
+    Ok(())
+}
+
+/// Build a constraint from the arguments and save it to the store
+fn add_constraint(
+    store: &dyn ConstraintStore,
+    config: &WorkspaceConfig,
+    args: &AddArgs,
+) -> Result<Constraint, ConstraintError> {
     // Parse constraint type
     let constraint_type = parse_constraint_type(&args.r#type)?;
 
-    // Validate category format
+    // Validate category format and workspace policy
     validate_category(&args.category)?;
+    config.check_category(&args.category)?;
+
+    // Fall back to the workspace default author
+    let author = args
+        .author
+        .clone()
+        .or_else(|| config.default_author.clone())
+        .ok_or_else(|| {
+            ConstraintError::Validation(
+                "No author given: pass --author or set default_author in the workspace config"
+                    .to_string(),
+            )
+        })?;
+
+    // Generate the ID with the workspace prefix unless one was given
+    let id = args.id.clone().unwrap_or_else(|| {
+        Constraint::generate_id_with_prefix(
+            &config.id_prefix,
+            &args.text,
+            &args.category,
+            &constraint_type,
+        )
+    });
 
     // Create constraint
-    let constraint = Constraint::new(crate::core::constraint::ConstraintParams {
+    let mut constraint = Constraint::new(crate::core::constraint::ConstraintParams {
         r#type: constraint_type,
         category: args.category.clone(),
         text: args.text.clone(),
-        author: args.author.clone(),
-        id: args.id.clone(),
+        author,
+        id: Some(id),
         tags: args.tags.clone(),
         priority: args.priority.clone(),
-        references: args.references.unwrap_or_default(),
-        verification: args.verification.clone(),
+        references: args.references.clone().unwrap_or_default(),
+        verification: parse_verification(
+            args.verification.clone(),
+            args.verification_spec.as_deref(),
+        )?,
+        depends_on: resolve_relation_targets(store, &args.depends_on)?,
+        supersedes: resolve_relation_targets(store, &args.supersedes)?,
+        conflicts_with: resolve_relation_targets(store, &args.conflicts_with)?,
+        lifecycle: parse_initial_lifecycle(&args.lifecycle)?,
     })?;
 
-    // Initialize workspace if needed
-    let workspace = Workspace::discover().or_else(
-        |_: crate::core::error::ConstraintError| -> Result<Workspace, Box<dyn std::error::Error>> {
-            // If workspace doesn't exist, create it
-            let workspace = Workspace::discover().unwrap_or_else(|_| {
-                // Create workspace in current directory
-                let current = std::env::current_dir().unwrap();
-                let newton_dir = current.join(".newton");
-                std::fs::create_dir_all(&newton_dir).unwrap();
-                std::fs::create_dir_all(newton_dir.join("constraints")).unwrap();
-                Workspace::new(newton_dir)
+    // Never overwrite an existing constraint
+    constraint.id = assign_id(store, &config.id_prefix, &constraint, args.id.is_some())?;
+    check_relations(store, &constraint)?;
+
+    // Start its history, then save the constraint
+    store.append_revision(&Revision::between(
+        RevisionAction::Created,
+        &constraint.author,
+        None,
+        Some(&constraint),
+    )?)?;
+    store.write_constraint(&constraint)?;
+
+    Ok(constraint)
+}
+
+/// Upper bound on salted re-hashes tried before giving up
+pub const MAX_ID_ATTEMPTS: u32 = 100;
+
+/// Choose the ID for a new constraint without ever reusing one that is taken
+///
+/// Content already stored in the target category is a duplicate, whatever
+/// its ID. Otherwise the deterministic candidate sequence for the content is
+/// walked until a free ID turns up. An explicit ID must itself be free.
+fn assign_id(
+    store: &dyn ConstraintStore,
+    prefix: &str,
+    constraint: &Constraint,
+    explicit: bool,
+) -> Result<String, ConstraintError> {
+    // Constraints added with `--id`, patched or moved sit off their candidate chain
+    if let Some(existing) = store
+        .read_category_constraints(&constraint.category)?
+        .into_iter()
+        .find(|existing| existing.same_content(constraint))
+    {
+        return Err(ConstraintError::Duplicate { id: existing.id });
+    }
+
+    let aliases = store.read_aliases()?;
+
+    if explicit {
+        if id_in_use(store, &aliases, &constraint.id)? {
+            return Err(ConstraintError::IdCollision {
+                id: constraint.id.clone(),
             });
-            workspace.ensure_structure()?;
-            Ok(workspace)
-        },
-    )?;
+        }
+        return Ok(constraint.id.clone());
+    }
 
-    // Save constraint
-    save_constraint(&workspace, &constraint)?;
+    for attempt in 0..MAX_ID_ATTEMPTS {
+        let candidate = Constraint::generate_alternate_id(
+            prefix,
+            &constraint.text,
+            &constraint.category,
+            &constraint.r#type,
+            attempt,
+        );
 
-    // Output result
-    if args.id.is_none() {
-        println!("Constraint added with ID: {}", constraint.id);
-    } else {
-        println!("Constraint added: {}", constraint.id);
+        if !id_in_use(store, &aliases, &candidate)? {
+            return Ok(candidate);
+        }
     }
 
-    Ok(())
+    Err(ConstraintError::Validation(format!(
+        "No free ID found for this content after {} attempts",
+        MAX_ID_ATTEMPTS
+    )))
 }
 
 /// Parse constraint type from string
-fn parse_constraint_type(type_str: &str) -> Result<ConstraintType, ConstraintError> {
+pub fn parse_constraint_type(type_str: &str) -> Result<ConstraintType, ConstraintError> {
     match type_str.to_uppercase().as_str() {
         "MUST" => Ok(ConstraintType::Must),
         "SHALL" => Ok(ConstraintType::Shall),
@@ -68,8 +165,37 @@ fn parse_constraint_type(type_str: &str) -> Result<ConstraintType, ConstraintErr
     }
 }
 
+/// Build a verification spec from `--verification` or `--verification-spec`
+pub fn parse_verification(
+    script: Option<String>,
+    spec_json: Option<&str>,
+) -> Result<Option<VerificationSpec>, ConstraintError> {
+    match (script, spec_json) {
+        (_, Some(json)) => serde_json::from_str(json).map(Some).map_err(|e| {
+            ConstraintError::Validation(format!("Invalid --verification-spec JSON: {}", e))
+        }),
+        (Some(script), None) => Ok(Some(VerificationSpec::shell(script))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Parse the lifecycle state a new constraint starts in
+///
+/// New constraints start as drafts, proposals or accepted; retired states are
+/// only reached through transitions.
+fn parse_initial_lifecycle(value: &str) -> Result<Lifecycle, ConstraintError> {
+    let lifecycle = Lifecycle::parse(value)?;
+    if lifecycle.is_retired() {
+        return Err(ConstraintError::Validation(format!(
+            "New constraints cannot start as {} (use draft, proposed or accepted)",
+            lifecycle
+        )));
+    }
+    Ok(lifecycle)
+}
+
 /// Validate category format
-fn validate_category(category: &str) -> Result<(), ConstraintError> {
+pub fn validate_category(category: &str) -> Result<(), ConstraintError> {
     let regex = regex::Regex::new(r"^[a-z0-9-]+$")
         .map_err(|_| ConstraintError::Validation("Invalid category regex".to_string()))?;
 
@@ -82,27 +208,30 @@ fn validate_category(category: &str) -> Result<(), ConstraintError> {
     Ok(())
 }
 
-/// Save constraint to storage
-fn save_constraint(workspace: &Workspace, constraint: &Constraint) -> Result<(), ConstraintError> {
-    use crate::storage::jsonl::JsonlStorage;
-    use std::fs;
-
-    // Ensure category directory exists
-    let category_dir = workspace.category_dir(&constraint.category);
-    fs::create_dir_all(&category_dir)?;
-
-    // Create storage instance
-    let storage = JsonlStorage::new(workspace.constraints_dir());
-
-    // Save constraint
-    storage.write_constraint(constraint)?;
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::alias::Alias;
+    use crate::storage::MemoryStore;
+
+    fn add_args(category: &str, text: &str) -> AddArgs {
+        AddArgs {
+            r#type: "MUST".to_string(),
+            category: category.to_string(),
+            text: text.to_string(),
+            author: None,
+            id: None,
+            tags: vec![],
+            priority: None,
+            references: None,
+            verification: None,
+            verification_spec: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: "accepted".to_string(),
+        }
+    }
 
     #[test]
     fn test_parse_constraint_type() {
@@ -133,6 +262,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_verification() {
+        assert_eq!(parse_verification(None, None).unwrap(), None);
+        assert_eq!(
+            parse_verification(Some("make check".to_string()), None).unwrap(),
+            Some(VerificationSpec::shell("make check"))
+        );
+
+        let spec = parse_verification(None, Some(r#"{"argv":["cargo","test"],"exit_codes":[0]}"#))
+            .unwrap()
+            .unwrap();
+        assert_eq!(spec.argv, vec!["cargo", "test"]);
+        assert!(parse_verification(None, Some("{\"argv\": ")).is_err());
+        assert!(parse_verification(None, Some(r#"{"shel":"true"}"#)).is_err());
+    }
+
     #[test]
     fn test_validate_category() {
         assert!(validate_category("security").is_ok());
@@ -143,4 +288,126 @@ mod tests {
         assert!(validate_category("category_name").is_err());
         assert!(validate_category("category@name").is_err());
     }
+
+    #[test]
+    fn test_add_constraint_uses_workspace_config() {
+        let store = MemoryStore::new();
+        let config = WorkspaceConfig {
+            default_author: Some("maintainer".to_string()),
+            allowed_categories: vec!["security".to_string()],
+            id_prefix: "acme".to_string(),
+            ..WorkspaceConfig::default()
+        };
+
+        let constraint =
+            add_constraint(&store, &config, &add_args("security", "Hash passwords")).unwrap();
+        assert_eq!(constraint.author, "maintainer");
+        assert!(constraint.id.starts_with("acme-"));
+        assert!(store.read_constraint_by_id(&constraint.id).is_ok());
+        assert_eq!(store.read_history(&constraint.id).unwrap().len(), 1);
+
+        let result = add_constraint(&store, &config, &add_args("testing", "Write tests"));
+        assert!(matches!(result, Err(ConstraintError::Validation(_))));
+    }
+
+    #[test]
+    fn test_add_constraint_requires_author() {
+        let store = MemoryStore::new();
+        let result = add_constraint(
+            &store,
+            &WorkspaceConfig::default(),
+            &add_args("security", "Hash passwords"),
+        );
+        assert!(matches!(result, Err(ConstraintError::Validation(_))));
+        assert!(store.read_all_constraints().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_detects_duplicates_and_collisions() {
+        let store = MemoryStore::new();
+        let config = WorkspaceConfig {
+            default_author: Some("maintainer".to_string()),
+            ..WorkspaceConfig::default()
+        };
+
+        let first =
+            add_constraint(&store, &config, &add_args("security", "Hash passwords")).unwrap();
+
+        // Identical content is a duplicate, whatever the ID
+        let result = add_constraint(&store, &config, &add_args("security", "Hash passwords"));
+        assert!(matches!(result, Err(ConstraintError::Duplicate { id }) if id == first.id));
+
+        // Different content whose natural ID is taken gets a salted alternate
+        let mut squatter = store.read_constraint_by_id(&first.id).unwrap();
+        store
+            .delete_constraint(&squatter.category, &squatter.id)
+            .unwrap();
+        squatter.text = "Something else entirely".to_string();
+        store.write_constraint(&squatter).unwrap();
+
+        let second =
+            add_constraint(&store, &config, &add_args("security", "Hash passwords")).unwrap();
+        assert_ne!(second.id, first.id);
+        assert_eq!(
+            store.read_constraint_by_id(&first.id).unwrap().text,
+            "Something else entirely"
+        );
+        assert!(matches!(
+            add_constraint(&store, &config, &add_args("security", "Hash passwords")),
+            Err(ConstraintError::Duplicate { id }) if id == second.id
+        ));
+
+        // Content stored under an unrelated ID is still a duplicate
+        let mut args = add_args("security", "Rotate keys");
+        args.id = Some("nt-custom".to_string());
+        let custom = add_constraint(&store, &config, &args).unwrap();
+        assert!(matches!(
+            add_constraint(&store, &config, &add_args("security", "Rotate keys")),
+            Err(ConstraintError::Duplicate { id }) if id == custom.id
+        ));
+
+        // An explicit ID that is already in use is refused
+        let mut args = add_args("testing", "Write tests");
+        args.id = Some(first.id.clone());
+        assert!(matches!(
+            add_constraint(&store, &config, &args),
+            Err(ConstraintError::IdCollision { .. })
+        ));
+
+        // Retired IDs stay reserved
+        store
+            .write_alias(&Alias::new("nt-old001", &second.id))
+            .unwrap();
+        args.id = Some("nt-old001".to_string());
+        assert!(matches!(
+            add_constraint(&store, &config, &args),
+            Err(ConstraintError::IdCollision { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_constraint_with_relations() {
+        let store = MemoryStore::new();
+        let config = WorkspaceConfig {
+            default_author: Some("maintainer".to_string()),
+            ..WorkspaceConfig::default()
+        };
+
+        let base =
+            add_constraint(&store, &config, &add_args("security", "Hash passwords")).unwrap();
+
+        // Targets may be given as unique prefixes and are stored as full IDs
+        let mut args = add_args("security", "Use argon2id for hashing");
+        args.depends_on = vec![base.id[3..6].to_string()];
+        let refined = add_constraint(&store, &config, &args).unwrap();
+        assert_eq!(refined.depends_on, vec![base.id.clone()]);
+
+        let mut args = add_args("security", "Store passwords in plain text");
+        args.conflicts_with = vec!["nt-zzzzzz".to_string()];
+        assert!(matches!(
+            add_constraint(&store, &config, &args),
+            Err(ConstraintError::NotFound { .. })
+        ));
+        assert_eq!(store.read_all_constraints().unwrap().len(), 2);
+    }
 }
@@ -0,0 +1,119 @@
+//! Implementation of the 'aliases' command
+
+use serde::Serialize;
+
+use crate::cli::args::AliasesArgs;
+use crate::core::alias::{resolve_chain, Alias};
+use crate::core::error::ConstraintError;
+use crate::storage::{self, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// An alias together with where it currently leads
+#[derive(Debug, Serialize)]
+struct AliasEntry {
+    #[serde(flatten)]
+    alias: Alias,
+
+    /// The ID the alias finally resolves to after following chains
+    resolves_to: String,
+
+    /// Whether the final ID matches no stored constraint
+    dangling: bool,
+}
+
+/// Run the aliases command
+pub fn run(args: AliasesArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let entries = list_aliases(store.as_ref(), args.dangling)?;
+
+    // Output results
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else if entries.is_empty() {
+        if args.dangling {
+            println!("No dangling aliases.");
+        } else {
+            println!("No aliases recorded.");
+        }
+    } else {
+        for entry in &entries {
+            println!(
+                "{} -> {}{}",
+                entry.alias.alias,
+                entry.resolves_to,
+                if entry.dangling { " (dangling)" } else { "" }
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolve every alias, optionally keeping only those that lead nowhere
+fn list_aliases(
+    store: &dyn ConstraintStore,
+    dangling_only: bool,
+) -> Result<Vec<AliasEntry>, ConstraintError> {
+    let aliases = store.read_aliases()?;
+    let mut entries = vec![];
+
+    for alias in &aliases {
+        let resolves_to =
+            resolve_chain(&aliases, &alias.alias).unwrap_or_else(|| alias.target.clone());
+
+        let dangling = match store.read_constraint_by_id(&resolves_to) {
+            Ok(_) => false,
+            Err(ConstraintError::NotFound { .. }) => true,
+            Err(e) => return Err(e),
+        };
+
+        if dangling || !dangling_only {
+            entries.push(AliasEntry {
+                alias: alias.clone(),
+                resolves_to,
+                dangling,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{Constraint, ConstraintParams, ConstraintType};
+    use crate::storage::MemoryStore;
+
+    #[test]
+    fn test_list_dangling_aliases() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            id: Some("nt-cur001".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        store
+            .write_alias(&Alias::new("nt-old001", "nt-cur001"))
+            .unwrap();
+        store
+            .write_alias(&Alias::new("nt-old002", "nt-gone01"))
+            .unwrap();
+
+        let all = list_aliases(&store, false).unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(!all[0].dangling);
+
+        let dangling = list_aliases(&store, true).unwrap();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].alias.alias, "nt-old002");
+    }
+}
@@ -0,0 +1,153 @@
+//! Implementation of the 'approve' command
+
+use crate::cli::args::ApproveArgs;
+use crate::core::alias::resolve_chain;
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::storage::{self, resolve_constraint, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// Run the approve command
+pub fn run(args: ApproveArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let constraint = approve_constraint(store.as_ref(), &args.id, &args.by)?;
+
+    // Output result
+    let required = workspace.config().approvals.required(constraint.r#type);
+    if required > 0 {
+        println!(
+            "Constraint {} approved by {} ({} of {} required approval(s)).",
+            constraint.id,
+            args.by.trim(),
+            constraint.current_approvals().len(),
+            required
+        );
+    } else {
+        println!(
+            "Constraint {} approved by {}.",
+            constraint.id,
+            args.by.trim()
+        );
+    }
+
+    Ok(())
+}
+
+/// Record a reviewer's sign-off on a stored constraint
+fn approve_constraint(
+    store: &dyn ConstraintStore,
+    id: &str,
+    reviewer: &str,
+) -> Result<Constraint, ConstraintError> {
+    let mut constraint = resolve_constraint(store, id)?;
+    if constraint.is_archived() || constraint.lifecycle.is_retired() {
+        return Err(ConstraintError::Validation(format!(
+            "Constraint {} is {} and cannot be approved",
+            constraint.id,
+            if constraint.is_archived() {
+                "archived".to_string()
+            } else {
+                constraint.lifecycle.to_string()
+            }
+        )));
+    }
+    let previous = constraint.clone();
+
+    let last_editor = last_editor(store, &constraint.id)?;
+    constraint.approve(reviewer, last_editor.as_deref())?;
+
+    store.append_revision(&Revision::between(
+        RevisionAction::Approved,
+        reviewer.trim(),
+        Some(&previous),
+        Some(&constraint),
+    )?)?;
+    store.write_constraint(&constraint)?;
+
+    Ok(constraint)
+}
+
+/// The actor of the latest revision that changed a constraint's content
+///
+/// Revisions recorded under retired IDs that now lead to it are included.
+fn last_editor(store: &dyn ConstraintStore, id: &str) -> Result<Option<String>, ConstraintError> {
+    let aliases = store.read_aliases()?;
+
+    // Retired IDs come first so the current ID wins same-second ties
+    let mut revisions = vec![];
+    for alias in &aliases {
+        if resolve_chain(&aliases, &alias.alias).as_deref() == Some(id) {
+            revisions.extend(store.read_history(&alias.alias)?);
+        }
+    }
+    revisions.extend(store.read_history(id)?);
+
+    Ok(revisions
+        .into_iter()
+        .filter(Revision::changes_content)
+        .max_by_key(|revision| revision.timestamp)
+        .map(|revision| revision.actor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::core::lifecycle::Lifecycle;
+    use crate::storage::MemoryStore;
+
+    #[test]
+    fn test_approve_constraint() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        assert!(approve_constraint(&store, &constraint.id, "author").is_err());
+        let approved = approve_constraint(&store, &constraint.id, "reviewer").unwrap();
+        assert_eq!(approved.current_approvals().len(), 1);
+
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, RevisionAction::Approved);
+        assert_eq!(history[0].actor, "reviewer");
+
+        // Nor can whoever last changed the content
+        let previous = store.read_constraint_by_id(&constraint.id).unwrap();
+        let mut edited = previous.clone();
+        edited.text = "Hash passwords with argon2id".to_string();
+        store.write_constraint(&edited).unwrap();
+        store
+            .append_revision(
+                &Revision::between(
+                    RevisionAction::Updated,
+                    "editor",
+                    Some(&previous),
+                    Some(&edited),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        assert!(approve_constraint(&store, &constraint.id, "editor").is_err());
+        approve_constraint(&store, &constraint.id, "reviewer").unwrap();
+
+        // Approving is not a content change, so it does not pass the restriction on
+        assert!(approve_constraint(&store, &constraint.id, "editor").is_err());
+
+        // Retired constraints are no longer up for review
+        let mut retired = store.read_constraint_by_id(&constraint.id).unwrap();
+        retired.lifecycle = Lifecycle::Deprecated;
+        store.write_constraint(&retired).unwrap();
+        assert!(approve_constraint(&store, &constraint.id, "other").is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! Implementation of the 'audit' command
+
+use crate::cli::args::{AuditArgs, AuditCommands, AuditVerifyArgs};
+use crate::storage::{AuditLog, ChainReport};
+use crate::utils::workspace::Workspace;
+
+/// Run the audit command
+pub fn run(args: AuditArgs) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        AuditCommands::Verify(args) => verify(args),
+    }
+}
+
+/// Walk the audit log's hash chain
+fn verify(args: AuditVerifyArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let report = AuditLog::new(workspace.audit_path()).verify()?;
+
+    // Output results
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        output_human(&report);
+    }
+
+    // Exit with appropriate code
+    if report.broken.is_some() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Output the chain report in human-readable format
+fn output_human(report: &ChainReport) {
+    match &report.broken {
+        None if report.entries == 0 => println!("Audit log is empty."),
+        None => {
+            println!("✅ Audit chain intact: {} entries.", report.entries);
+            if let Some(head) = &report.head {
+                println!("   Head: {}", head);
+            }
+        }
+        Some(broken) => {
+            println!(
+                "❌ Audit chain broken at line {}{}: {}",
+                broken.line,
+                broken
+                    .seq
+                    .map(|seq| format!(" (entry {})", seq))
+                    .unwrap_or_default(),
+                broken.reason
+            );
+            println!("   Entries chained before the break: {}", report.entries);
+        }
+    }
+}
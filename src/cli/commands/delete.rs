@@ -1,24 +1,267 @@
 //! Implementation of the 'delete' command
 
 use crate::cli::args::DeleteArgs;
-use crate::storage::jsonl::JsonlStorage;
+use crate::core::alias::resolve_chain;
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::core::relation::RelationKind;
+use crate::storage::{self, incoming_relations, resolve_constraint, ConstraintStore};
 use crate::utils::workspace::Workspace;
 
 /// Run the delete command
 pub fn run(args: DeleteArgs) -> Result<(), Box<dyn std::error::Error>> {
     // Discover workspace
     let workspace = Workspace::discover()?;
-    let storage = JsonlStorage::new(workspace.constraints_dir());
+    let store = storage::open(&workspace)?;
 
-    // Read the constraint first to get its category (for user feedback)
-    let constraint = storage.read_constraint_by_id(&args.id)?;
-    let category = constraint.category.clone();
-
-    // Delete the constraint
-    storage.delete_constraint(&category, &args.id)?;
+    let actor = workspace.config().actor(args.actor.as_deref());
 
     // Output result
-    println!("Constraint {} deleted successfully.", args.id);
+    if args.purge {
+        let (constraint, unlinked) =
+            purge_constraint(store.as_ref(), &actor, &args.id, args.force)?;
+        println!("Constraint {} permanently deleted.", constraint.id);
+        if !unlinked.is_empty() {
+            println!("Removed references to it from {}.", unlinked.join(", "));
+        }
+    } else {
+        let constraint = archive_constraint(store.as_ref(), &actor, &args.id, args.force)?;
+        println!(
+            "Constraint {} archived (use 'constraint restore {}' to undo).",
+            constraint.id, constraint.id
+        );
+    }
 
     Ok(())
 }
+
+/// Archive a constraint and record the revision, returning the archived record
+fn archive_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+    force: bool,
+) -> Result<Constraint, ConstraintError> {
+    let previous = resolve_constraint(store, id)?;
+    check_dependents(store, &previous, force)?;
+
+    let mut constraint = previous.clone();
+    constraint.archive().map_err(|_| {
+        ConstraintError::Validation(format!(
+            "Constraint {} is already archived (use --purge to remove it permanently)",
+            previous.id
+        ))
+    })?;
+
+    store.append_revision(&Revision::between(
+        RevisionAction::Archived,
+        actor,
+        Some(&previous),
+        Some(&constraint),
+    )?)?;
+    store.write_constraint(&constraint)?;
+
+    Ok(constraint)
+}
+
+/// Remove a constraint from the store and record the revision
+///
+/// Relationships other constraints hold to it are removed too, each with its
+/// own revision. Returns the removed record and the IDs of the constraints
+/// that referenced it.
+fn purge_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+    force: bool,
+) -> Result<(Constraint, Vec<String>), ConstraintError> {
+    // Read the constraint first to get its category
+    let constraint = resolve_constraint(store, id)?;
+    check_dependents(store, &constraint, force)?;
+
+    // Delete the constraint
+    store.append_revision(&Revision::between(
+        RevisionAction::Deleted,
+        actor,
+        Some(&constraint),
+        None,
+    )?)?;
+    store.delete_constraint(&constraint.category, &constraint.id)?;
+
+    let unlinked = unlink_references(store, actor, &constraint.id)?;
+    Ok((constraint, unlinked))
+}
+
+/// Remove every relationship pointing at a purged ID, directly or through an alias
+///
+/// Returns the IDs of the constraints that changed.
+fn unlink_references(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+) -> Result<Vec<String>, ConstraintError> {
+    let aliases = store.read_aliases()?;
+    let points_at_purged =
+        |target: &str| target == id || resolve_chain(&aliases, target).as_deref() == Some(id);
+
+    let mut unlinked = vec![];
+    for previous in store.read_all_constraints()? {
+        let mut constraint = previous.clone();
+        if !constraint.remove_relations(points_at_purged) {
+            continue;
+        }
+
+        store.append_revision(&Revision::between(
+            RevisionAction::Updated,
+            actor,
+            Some(&previous),
+            Some(&constraint),
+        )?)?;
+        store.write_constraint(&constraint)?;
+        unlinked.push(constraint.id);
+    }
+
+    Ok(unlinked)
+}
+
+/// Refuse to remove a constraint that live constraints still depend on
+fn check_dependents(
+    store: &dyn ConstraintStore,
+    constraint: &Constraint,
+    force: bool,
+) -> Result<(), ConstraintError> {
+    if force {
+        return Ok(());
+    }
+
+    let dependents: Vec<String> = incoming_relations(store, &constraint.id)?
+        .into_iter()
+        .filter(|r| r.kind == RelationKind::DependsOn)
+        .map(|r| r.source)
+        .collect();
+
+    if dependents.is_empty() {
+        return Ok(());
+    }
+
+    Err(ConstraintError::Validation(format!(
+        "Constraint {} is depended on by {} (use --force to delete it anyway)",
+        constraint.id,
+        dependents.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::MemoryStore;
+
+    fn stored_constraint(store: &MemoryStore) -> Constraint {
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Test constraint".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        constraint
+    }
+
+    #[test]
+    fn test_delete_constraint() {
+        let store = MemoryStore::new();
+        let constraint = stored_constraint(&store);
+
+        purge_constraint(&store, "author", &constraint.id, false).unwrap();
+        assert!(store.read_all_constraints().unwrap().is_empty());
+
+        // The removed values stay in the history
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, RevisionAction::Deleted);
+        assert_eq!(
+            history[0].old.get("text"),
+            Some(&serde_json::Value::from("Test constraint"))
+        );
+
+        let result = purge_constraint(&store, "author", &constraint.id, false);
+        assert!(matches!(result, Err(ConstraintError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_archive_keeps_constraint() {
+        let store = MemoryStore::new();
+        let constraint = stored_constraint(&store);
+
+        archive_constraint(&store, "author", &constraint.id, false).unwrap();
+        assert!(store
+            .read_constraint_by_id(&constraint.id)
+            .unwrap()
+            .is_archived());
+        assert_eq!(
+            store.read_history(&constraint.id).unwrap()[0].action,
+            RevisionAction::Archived
+        );
+
+        // Archiving twice is refused; purging an archived constraint works
+        assert!(matches!(
+            archive_constraint(&store, "author", &constraint.id, false),
+            Err(ConstraintError::Validation(_))
+        ));
+        purge_constraint(&store, "author", &constraint.id, false).unwrap();
+        assert!(store.read_all_constraints().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_refuses_to_orphan_dependents() {
+        let store = MemoryStore::new();
+        let constraint = stored_constraint(&store);
+        let dependent = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Should,
+            category: "security".to_string(),
+            text: "Dependent constraint".to_string(),
+            author: "author".to_string(),
+            depends_on: vec![constraint.id.clone()],
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&dependent).unwrap();
+
+        for result in [
+            archive_constraint(&store, "author", &constraint.id, false),
+            purge_constraint(&store, "author", &constraint.id, false).map(|(c, _)| c),
+        ] {
+            match result {
+                Err(ConstraintError::Validation(message)) => {
+                    assert!(message.contains(&dependent.id));
+                }
+                other => panic!("expected a refusal, got {:?}", other),
+            }
+        }
+
+        // Forcing it removes the dangling references, with a revision each
+        let mut rival = dependent.clone();
+        rival.id = "nt-rival1".to_string();
+        rival.depends_on = vec![];
+        rival.conflicts_with = vec!["nt-oldid1".to_string()];
+        store.write_constraint(&rival).unwrap();
+        store
+            .write_alias(&crate::core::alias::Alias::new("nt-oldid1", &constraint.id))
+            .unwrap();
+
+        let (_, unlinked) = purge_constraint(&store, "author", &constraint.id, true).unwrap();
+        assert_eq!(unlinked.len(), 2);
+        for id in [&dependent.id, &rival.id] {
+            let stored = store.read_constraint_by_id(id).unwrap();
+            assert!(stored.relations().is_empty());
+            assert_eq!(
+                store.read_history(id).unwrap()[0].action,
+                RevisionAction::Updated
+            );
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Implementation of the 'graph' command
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::cli::args::GraphArgs;
+use crate::cli::commands::list::list_constraints;
+use crate::core::constraint::{Constraint, ConstraintType};
+use crate::core::error::ConstraintError;
+use crate::core::relation::RelationKind;
+use crate::storage;
+use crate::utils::workspace::Workspace;
+
+/// Longest constraint text shown in a node label
+const MAX_LABEL_TEXT: usize = 40;
+
+/// Supported graph output formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+/// What an edge in the graph stands for
+#[derive(Debug, Clone, PartialEq)]
+enum EdgeKind {
+    /// A typed relationship, drawn from source to target
+    Relation(RelationKind),
+    /// Tags shared by both ends, drawn without direction
+    SharedTags(Vec<String>),
+}
+
+/// A single edge between two constraints in the graph
+#[derive(Debug, Clone, PartialEq)]
+struct Edge {
+    from: String,
+    to: String,
+    kind: EdgeKind,
+}
+
+/// Constraints grouped by category, with the edges between them
+#[derive(Debug)]
+struct Graph {
+    clusters: BTreeMap<String, Vec<Constraint>>,
+    edges: Vec<Edge>,
+}
+
+/// Run the graph command
+pub fn run(args: GraphArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let format = parse_graph_format(&args.format)?;
+
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let constraints = list_constraints(
+        store.as_ref(),
+        args.category.as_deref(),
+        args.include_archived,
+    )?;
+    let graph = build_graph(constraints);
+
+    match format {
+        GraphFormat::Dot => print!("{}", render_dot(&graph)),
+        GraphFormat::Mermaid => print!("{}", render_mermaid(&graph)),
+    }
+
+    Ok(())
+}
+
+/// Parse graph format from string
+fn parse_graph_format(format: &str) -> Result<GraphFormat, ConstraintError> {
+    match format.to_lowercase().as_str() {
+        "dot" => Ok(GraphFormat::Dot),
+        "mermaid" => Ok(GraphFormat::Mermaid),
+        _ => Err(ConstraintError::Validation(format!(
+            "Unsupported graph format '{}' (expected dot or mermaid)",
+            format
+        ))),
+    }
+}
+
+/// Group constraints into clusters and collect the edges between them
+///
+/// Only edges whose both ends are in the selection are kept, so a filtered
+/// graph never points at nodes it does not draw.
+fn build_graph(constraints: Vec<Constraint>) -> Graph {
+    let ids: BTreeSet<String> = constraints.iter().map(|c| c.id.clone()).collect();
+    let mut edges = vec![];
+
+    for constraint in &constraints {
+        for relation in constraint.relations() {
+            if ids.contains(&relation.target) {
+                edges.push(Edge {
+                    from: relation.source,
+                    to: relation.target,
+                    kind: EdgeKind::Relation(relation.kind),
+                });
+            }
+        }
+    }
+
+    // One undirected edge per pair of constraints sharing at least one tag
+    let mut shared: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for (i, a) in constraints.iter().enumerate() {
+        for b in &constraints[i + 1..] {
+            let (first, second) = if a.id < b.id { (a, b) } else { (b, a) };
+            for tag in first.tags.iter().filter(|t| second.tags.contains(t)) {
+                shared
+                    .entry((first.id.clone(), second.id.clone()))
+                    .or_default()
+                    .insert(tag.clone());
+            }
+        }
+    }
+    edges.extend(shared.into_iter().map(|((from, to), tags)| Edge {
+        from,
+        to,
+        kind: EdgeKind::SharedTags(tags.into_iter().collect()),
+    }));
+
+    let mut clusters: BTreeMap<String, Vec<Constraint>> = BTreeMap::new();
+    for constraint in constraints {
+        clusters
+            .entry(constraint.category.clone())
+            .or_default()
+            .push(constraint);
+    }
+    for nodes in clusters.values_mut() {
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+    }
+
+    Graph { clusters, edges }
+}
+
+/// Fill colour used for each constraint type
+fn type_color(r#type: ConstraintType) -> &'static str {
+    match r#type {
+        ConstraintType::Must => "#f8d7da",
+        ConstraintType::Shall => "#fde2c8",
+        ConstraintType::Should => "#fff3cd",
+        ConstraintType::May => "#d4edda",
+        ConstraintType::Forbidden => "#343a40",
+    }
+}
+
+/// Font colour readable on the type's fill colour
+fn type_font_color(r#type: ConstraintType) -> &'static str {
+    match r#type {
+        ConstraintType::Forbidden => "#ffffff",
+        _ => "#000000",
+    }
+}
+
+/// Border width used for each priority, so P1 stands out
+fn priority_width(priority: Option<&str>) -> u32 {
+    match priority {
+        Some("P1") => 3,
+        Some("P2") => 2,
+        _ => 1,
+    }
+}
+
+/// Label lines shown in a node: ID, type and priority, shortened text
+fn node_label_lines(constraint: &Constraint) -> [String; 3] {
+    let mut heading = constraint.r#type.as_str().to_string();
+    if let Some(priority) = &constraint.priority {
+        heading.push(' ');
+        heading.push_str(priority);
+    }
+
+    let text = if constraint.text.chars().count() > MAX_LABEL_TEXT {
+        let short: String = constraint.text.chars().take(MAX_LABEL_TEXT - 3).collect();
+        format!("{}...", short.trim_end())
+    } else {
+        constraint.text.clone()
+    };
+
+    [constraint.id.clone(), heading, text]
+}
+
+/// Caption shown on an edge
+fn edge_label(kind: &EdgeKind) -> String {
+    match kind {
+        EdgeKind::Relation(kind) => kind.to_string(),
+        EdgeKind::SharedTags(tags) => tags.join(", "),
+    }
+}
+
+/// Escape a string for use inside a double-quoted DOT string
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render the graph in Graphviz DOT syntax
+fn render_dot(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("digraph constraints {\n");
+    out.push_str("  rankdir=LR;\n");
+    out.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+    out.push_str("  edge [fontname=\"Helvetica\", fontsize=10];\n");
+
+    for (category, nodes) in &graph.clusters {
+        out.push('\n');
+        out.push_str(&format!(
+            "  subgraph \"cluster_{}\" {{\n",
+            escape_dot(category)
+        ));
+        out.push_str(&format!("    label=\"{}\";\n", escape_dot(category)));
+
+        for constraint in nodes {
+            let label = node_label_lines(constraint)
+                .iter()
+                .map(|line| escape_dot(line))
+                .collect::<Vec<_>>()
+                .join("\\n");
+            let style = if constraint.is_archived() {
+                ", style=\"rounded,filled,dashed\""
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\", fillcolor=\"{}\", fontcolor=\"{}\", penwidth={}{}];\n",
+                escape_dot(&constraint.id),
+                label,
+                type_color(constraint.r#type),
+                type_font_color(constraint.r#type),
+                priority_width(constraint.priority.as_deref()),
+                style
+            ));
+        }
+
+        out.push_str("  }\n");
+    }
+
+    if !graph.edges.is_empty() {
+        out.push('\n');
+    }
+    for edge in &graph.edges {
+        let attributes = match &edge.kind {
+            EdgeKind::Relation(RelationKind::DependsOn) => "",
+            EdgeKind::Relation(RelationKind::Supersedes) => ", style=bold",
+            EdgeKind::Relation(RelationKind::ConflictsWith) => {
+                ", color=\"#c0392b\", fontcolor=\"#c0392b\", dir=both, arrowhead=tee, arrowtail=tee"
+            }
+            EdgeKind::SharedTags(_) => ", style=dashed, color=\"#888888\", dir=none",
+        };
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\" [label=\"{}\"{}];\n",
+            escape_dot(&edge.from),
+            escape_dot(&edge.to),
+            escape_dot(&edge_label(&edge.kind)),
+            attributes
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Turn an ID or category into a Mermaid-safe identifier
+fn mermaid_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Escape text for use inside a quoted Mermaid label
+fn escape_mermaid(value: &str) -> String {
+    value
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+/// Render the graph as a Mermaid flowchart
+fn render_mermaid(graph: &Graph) -> String {
+    let mut out = String::new();
+    out.push_str("flowchart LR\n");
+
+    for r#type in [
+        ConstraintType::Must,
+        ConstraintType::Shall,
+        ConstraintType::Should,
+        ConstraintType::May,
+        ConstraintType::Forbidden,
+    ] {
+        out.push_str(&format!(
+            "  classDef {} fill:{},color:{},stroke:#333333\n",
+            r#type.as_str().to_lowercase(),
+            type_color(r#type),
+            type_font_color(r#type)
+        ));
+    }
+
+    let mut styles = vec![];
+    for (category, nodes) in &graph.clusters {
+        out.push_str(&format!(
+            "  subgraph cat_{}[\"{}\"]\n",
+            mermaid_id(category),
+            escape_mermaid(category)
+        ));
+
+        for constraint in nodes {
+            let id = mermaid_id(&constraint.id);
+            let label = node_label_lines(constraint)
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect::<Vec<_>>()
+                .join("<br/>");
+            out.push_str(&format!(
+                "    {}(\"{}\"):::{}\n",
+                id,
+                label,
+                constraint.r#type.as_str().to_lowercase()
+            ));
+
+            let mut style = format!(
+                "stroke-width:{}px",
+                priority_width(constraint.priority.as_deref())
+            );
+            if constraint.is_archived() {
+                style.push_str(",stroke-dasharray:5 5");
+            }
+            styles.push(format!("  style {} {}\n", id, style));
+        }
+
+        out.push_str("  end\n");
+    }
+
+    for edge in &graph.edges {
+        let arrow = match &edge.kind {
+            EdgeKind::Relation(RelationKind::DependsOn) => "-->",
+            EdgeKind::Relation(RelationKind::Supersedes) => "==>",
+            EdgeKind::Relation(RelationKind::ConflictsWith) => "x--x",
+            EdgeKind::SharedTags(_) => "-.-",
+        };
+        out.push_str(&format!(
+            "  {} {}|\"{}\"| {}\n",
+            mermaid_id(&edge.from),
+            arrow,
+            escape_mermaid(&edge_label(&edge.kind)),
+            mermaid_id(&edge.to)
+        ));
+    }
+
+    for style in styles {
+        out.push_str(&style);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::ConstraintParams;
+
+    fn constraint(
+        id: &str,
+        category: &str,
+        r#type: ConstraintType,
+        tags: &[&str],
+        depends_on: &[&str],
+    ) -> Constraint {
+        Constraint::new(ConstraintParams {
+            r#type,
+            category: category.to_string(),
+            text: format!("Rule {} is \"quoted\"", id),
+            author: "author".to_string(),
+            id: Some(id.to_string()),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            priority: Some("P1".to_string()),
+            depends_on: depends_on.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn sample_graph() -> Graph {
+        build_graph(vec![
+            constraint(
+                "nt-aaa001",
+                "security",
+                ConstraintType::Must,
+                &["auth"],
+                &[],
+            ),
+            constraint(
+                "nt-bbb001",
+                "security",
+                ConstraintType::Forbidden,
+                &["auth", "crypto"],
+                &["nt-aaa001", "nt-out001"],
+            ),
+            constraint("nt-ccc001", "testing", ConstraintType::May, &[], &[]),
+        ])
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let graph = sample_graph();
+        assert_eq!(graph.clusters.len(), 2);
+        assert_eq!(graph.clusters["security"].len(), 2);
+
+        // The edge to a constraint outside the selection is dropped
+        assert_eq!(
+            graph.edges,
+            vec![
+                Edge {
+                    from: "nt-bbb001".to_string(),
+                    to: "nt-aaa001".to_string(),
+                    kind: EdgeKind::Relation(RelationKind::DependsOn),
+                },
+                Edge {
+                    from: "nt-aaa001".to_string(),
+                    to: "nt-bbb001".to_string(),
+                    kind: EdgeKind::SharedTags(vec!["auth".to_string()]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let dot = render_dot(&sample_graph());
+        assert!(dot.starts_with("digraph constraints {\n"));
+        assert!(dot.contains("subgraph \"cluster_security\" {"));
+        assert!(dot.contains(
+            "\"nt-bbb001\" [label=\"nt-bbb001\\nFORBIDDEN P1\\nRule nt-bbb001 is \\\"quoted\\\"\", fillcolor=\"#343a40\", fontcolor=\"#ffffff\", penwidth=3];"
+        ));
+        assert!(dot.contains("\"nt-bbb001\" -> \"nt-aaa001\" [label=\"depends-on\"];"));
+        assert!(dot.contains(
+            "\"nt-aaa001\" -> \"nt-bbb001\" [label=\"auth\", style=dashed, color=\"#888888\", dir=none];"
+        ));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let mermaid = render_mermaid(&sample_graph());
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  subgraph cat_testing[\"testing\"]\n"));
+        assert!(mermaid.contains(
+            "    nt_ccc001(\"nt-ccc001<br/>MAY P1<br/>Rule nt-ccc001 is #quot;quoted#quot;\"):::may\n"
+        ));
+        assert!(mermaid.contains("  nt_bbb001 -->|\"depends-on\"| nt_aaa001\n"));
+        assert!(mermaid.contains("  nt_aaa001 -.-|\"auth\"| nt_bbb001\n"));
+        assert!(mermaid.contains("  style nt_aaa001 stroke-width:3px\n"));
+    }
+
+    #[test]
+    fn test_parse_graph_format() {
+        assert_eq!(parse_graph_format("DOT").unwrap(), GraphFormat::Dot);
+        assert_eq!(parse_graph_format("mermaid").unwrap(), GraphFormat::Mermaid);
+        assert!(parse_graph_format("svg").is_err());
+    }
+}
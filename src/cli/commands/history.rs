@@ -0,0 +1,271 @@
+//! Implementation of the 'history' command
+
+use crate::cli::args::HistoryArgs;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::core::alias::{resolve_chain, Alias};
+use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
+use crate::storage::{self, resolve_constraint, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// Run the history command
+pub fn run(args: HistoryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let (id, revisions) = constraint_history(store.as_ref(), &args.id)?;
+
+    // Output results
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
+        println!("{}", serde_json::to_string_pretty(&revisions)?);
+    } else {
+        output_human(&id, &revisions);
+    }
+
+    Ok(())
+}
+
+/// Collect the revisions of a constraint, which may since have been deleted
+///
+/// Revisions recorded under any retired ID that now leads to the same
+/// constraint are included, so re-identification does not split the history.
+/// Returns the full ID together with its revisions.
+fn constraint_history(
+    store: &dyn ConstraintStore,
+    input: &str,
+) -> Result<(String, Vec<Revision>), ConstraintError> {
+    // Deleted constraints can only be found by their full ID
+    let (id, exists) = match resolve_constraint(store, input) {
+        Ok(constraint) => (constraint.id, true),
+        Err(ConstraintError::NotFound { .. }) => (input.to_string(), false),
+        Err(e) => return Err(e),
+    };
+
+    let aliases = store.read_aliases()?;
+    let current = resolve_chain(&aliases, &id).unwrap_or(id);
+
+    // Timestamps have second precision; same-second revisions are ordered by
+    // how many renames ago their ID was retired, then in recorded order
+    let mut revisions = vec![];
+    for alias in &aliases {
+        if resolve_chain(&aliases, &alias.alias).as_deref() == Some(current.as_str()) {
+            let renames = renames_since(&aliases, &alias.alias);
+            revisions.extend(
+                store
+                    .read_history(&alias.alias)?
+                    .into_iter()
+                    .map(|r| (renames, r)),
+            );
+        }
+    }
+    revisions.extend(store.read_history(&current)?.into_iter().map(|r| (0, r)));
+    revisions.sort_by_key(|(renames, r)| (r.timestamp, Reverse(*renames)));
+    let revisions: Vec<Revision> = revisions.into_iter().map(|(_, r)| r).collect();
+
+    // Distinguish an unknown ID from a constraint with no recorded changes
+    if revisions.is_empty() && !exists {
+        return Err(ConstraintError::NotFound {
+            id: input.to_string(),
+        });
+    }
+
+    Ok((current, revisions))
+}
+
+/// Number of renames leading from a retired ID to the current one
+fn renames_since(aliases: &[Alias], id: &str) -> usize {
+    let mut renames = 0;
+    let mut current = id;
+    let mut seen = HashSet::from([id]);
+    while let Some(alias) = aliases.iter().find(|a| a.alias == current) {
+        if !seen.insert(alias.target.as_str()) {
+            break;
+        }
+        current = &alias.target;
+        renames += 1;
+    }
+    renames
+}
+
+/// Output revisions in human-readable format
+fn output_human(id: &str, revisions: &[Revision]) {
+    if revisions.is_empty() {
+        println!("No history recorded for {}.", id);
+        return;
+    }
+
+    println!("History of {} ({} revision(s)):", id, revisions.len());
+
+    for revision in revisions {
+        println!();
+        println!(
+            "{} {} by {}",
+            revision.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+            revision.action,
+            revision.actor
+        );
+
+        for change in revision.changes() {
+            println!(
+                "  {}: {} -> {}",
+                change.field,
+                format_value(change.old.as_ref()),
+                format_value(change.new.as_ref())
+            );
+        }
+    }
+}
+
+/// Format a field value for display
+fn format_value(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => "(none)".to_string(),
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{Constraint, ConstraintParams, ConstraintType};
+    use crate::core::history::RevisionAction;
+    use crate::storage::MemoryStore;
+    use chrono::SubsecRound;
+
+    #[test]
+    fn test_constraint_history() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        // Existing constraint without recorded changes
+        assert!(constraint_history(&store, &constraint.id)
+            .unwrap()
+            .1
+            .is_empty());
+
+        // Deleted constraints keep their history
+        store
+            .delete_constraint(&constraint.category, &constraint.id)
+            .unwrap();
+        store
+            .append_revision(
+                &Revision::between(RevisionAction::Deleted, "author", Some(&constraint), None)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            constraint_history(&store, &constraint.id).unwrap().1.len(),
+            1
+        );
+
+        assert!(matches!(
+            constraint_history(&store, "nt-000000"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_history_follows_aliases() {
+        let store = MemoryStore::new();
+        let old = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            id: Some("nt-old001".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut renamed = old.clone();
+        renamed.id = "nt-new001".to_string();
+        store.write_constraint(&renamed).unwrap();
+        store
+            .write_alias(&crate::core::alias::Alias::new("nt-old001", "nt-new001"))
+            .unwrap();
+        store
+            .append_revision(
+                &Revision::between(RevisionAction::Created, "author", None, Some(&old)).unwrap(),
+            )
+            .unwrap();
+        store
+            .append_revision(
+                &Revision::between(
+                    RevisionAction::Reidentified,
+                    "author",
+                    Some(&old),
+                    Some(&renamed),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        // Either ID shows the full history
+        for input in ["nt-old001", "nt-new001", "new"] {
+            let (id, revisions) = constraint_history(&store, input).unwrap();
+            assert_eq!(id, "nt-new001");
+            assert_eq!(revisions.len(), 2);
+            assert_eq!(revisions[0].action, RevisionAction::Created);
+        }
+    }
+
+    #[test]
+    fn test_same_second_history_follows_renames() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            id: Some("nt-aaa001".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        // Renamed twice within one second, against the alphabetical order of the IDs
+        let chain = ["nt-zzz001", "nt-mmm001", "nt-aaa001"];
+        let timestamp = chrono::Utc::now().trunc_subsecs(0);
+        for pair in chain.windows(2) {
+            store.write_alias(&Alias::new(pair[0], pair[1])).unwrap();
+        }
+        for id in chain {
+            let mut revision =
+                Revision::between(RevisionAction::Updated, "author", None, Some(&constraint))
+                    .unwrap();
+            revision.constraint_id = id.to_string();
+            revision.timestamp = timestamp;
+            store.append_revision(&revision).unwrap();
+        }
+
+        let (_, revisions) = constraint_history(&store, "nt-aaa001").unwrap();
+        assert_eq!(
+            revisions
+                .iter()
+                .map(|r| r.constraint_id.as_str())
+                .collect::<Vec<_>>(),
+            chain
+        );
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(None), "(none)");
+        assert_eq!(format_value(Some(&serde_json::Value::Null)), "(none)");
+        assert_eq!(format_value(Some(&serde_json::json!("P1"))), "\"P1\"");
+        assert_eq!(
+            format_value(Some(&serde_json::json!(["a", "b"]))),
+            "[\"a\",\"b\"]"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Implementation of the 'init' command
+
+use crate::cli::args::InitArgs;
+use crate::core::error::ConstraintError;
+use crate::utils::config::WorkspaceConfig;
+use crate::utils::workspace::Workspace;
+
+/// Run the init command
+pub fn run(args: InitArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let config = build_config(&args)?;
+
+    let current = std::env::current_dir()?;
+    let workspace = Workspace::init(&current, config, args.force)?;
+
+    println!(
+        "Initialized constraints workspace at {}",
+        workspace.config_path().display()
+    );
+
+    Ok(())
+}
+
+/// Build the workspace configuration from command-line arguments
+fn build_config(args: &InitArgs) -> Result<WorkspaceConfig, ConstraintError> {
+    let category_regex = regex::Regex::new(r"^[a-z0-9-]+$")
+        .map_err(|_| ConstraintError::Validation("Invalid category regex".to_string()))?;
+
+    for category in &args.categories {
+        if !category_regex.is_match(category) {
+            return Err(ConstraintError::Validation(format!(
+                "Category '{}' must be lowercase alphanumeric with hyphens only",
+                category
+            )));
+        }
+    }
+
+    let config = WorkspaceConfig {
+        default_author: args.author.clone(),
+        allowed_categories: args.categories.clone(),
+        id_prefix: args.id_prefix.clone(),
+        default_format: args.format.clone(),
+        ..WorkspaceConfig::default()
+    };
+    config.validate()?;
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_args() -> InitArgs {
+        InitArgs {
+            author: Some("maintainer".to_string()),
+            categories: vec!["security".to_string(), "testing".to_string()],
+            id_prefix: "nt".to_string(),
+            format: "human".to_string(),
+            force: false,
+        }
+    }
+
+    #[test]
+    fn test_build_config() {
+        let config = build_config(&init_args()).unwrap();
+        assert_eq!(config.default_author.as_deref(), Some("maintainer"));
+        assert_eq!(config.allowed_categories, vec!["security", "testing"]);
+    }
+
+    #[test]
+    fn test_build_config_rejects_invalid_values() {
+        let mut args = init_args();
+        args.categories = vec!["Bad Category".to_string()];
+        assert!(matches!(
+            build_config(&args),
+            Err(ConstraintError::Validation(_))
+        ));
+
+        let mut args = init_args();
+        args.id_prefix = "n-t".to_string();
+        assert!(matches!(
+            build_config(&args),
+            Err(ConstraintError::Config(_))
+        ));
+    }
+}
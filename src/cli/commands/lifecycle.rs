@@ -0,0 +1,220 @@
+//! Implementation of the lifecycle commands ('propose', 'accept', 'withdraw',
+//! 'deprecate' and 'supersede')
+
+use crate::cli::args::{SupersedeArgs, TransitionArgs};
+use crate::core::constraint::{Constraint, ConstraintUpdate};
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::core::lifecycle::Lifecycle;
+use crate::storage::{self, check_relations, resolve_constraint, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// Run a lifecycle transition command
+pub fn run(args: TransitionArgs, lifecycle: Lifecycle) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let actor = workspace.config().actor(args.actor.as_deref());
+    let constraint = transition_constraint(store.as_ref(), &actor, &args.id, lifecycle)?;
+
+    // Output result
+    println!(
+        "Constraint {} is now {}.",
+        constraint.id, constraint.lifecycle
+    );
+
+    Ok(())
+}
+
+/// Run the supersede command
+pub fn run_supersede(args: SupersedeArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let actor = workspace.config().actor(args.actor.as_deref());
+    let (old, new) = supersede_constraint(store.as_ref(), &actor, &args.id, &args.by)?;
+
+    // Output result
+    println!("Constraint {} is now superseded by {}.", old.id, new.id);
+
+    Ok(())
+}
+
+/// Move a stored constraint to another lifecycle state and record the revision
+fn transition_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+    lifecycle: Lifecycle,
+) -> Result<Constraint, ConstraintError> {
+    let previous = resolve_constraint(store, id)?;
+    let constraint = apply_transition(&previous, lifecycle)?;
+
+    store.append_revision(&Revision::between(
+        RevisionAction::Transitioned,
+        actor,
+        Some(&previous),
+        Some(&constraint),
+    )?)?;
+    store.write_constraint(&constraint)?;
+
+    Ok(constraint)
+}
+
+/// Supersede `id` with `by`, recording the relationship on the replacement
+///
+/// Returns the superseded constraint and its replacement.
+fn supersede_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+    by: &str,
+) -> Result<(Constraint, Constraint), ConstraintError> {
+    let previous_old = resolve_constraint(store, id)?;
+    let previous_new = resolve_constraint(store, by)?;
+
+    if previous_new.lifecycle.is_retired() || previous_new.is_archived() {
+        return Err(ConstraintError::Validation(format!(
+            "Constraint {} is {} and cannot replace another constraint",
+            previous_new.id,
+            if previous_new.is_archived() {
+                "archived".to_string()
+            } else {
+                previous_new.lifecycle.to_string()
+            }
+        )));
+    }
+
+    let old = apply_transition(&previous_old, Lifecycle::Superseded)?;
+
+    let mut new = previous_new.clone();
+    if !new.supersedes.contains(&old.id) {
+        let mut supersedes = new.supersedes.clone();
+        supersedes.push(old.id.clone());
+        new.update(ConstraintUpdate {
+            supersedes: Some(supersedes),
+            ..Default::default()
+        })?;
+        check_relations(store, &new)?;
+    }
+
+    store.append_revision(&Revision::between(
+        RevisionAction::Transitioned,
+        actor,
+        Some(&previous_old),
+        Some(&old),
+    )?)?;
+    store.write_constraint(&old)?;
+
+    let revision = Revision::between(
+        RevisionAction::Updated,
+        actor,
+        Some(&previous_new),
+        Some(&new),
+    )?;
+    if !revision.is_empty() {
+        store.append_revision(&revision)?;
+        store.write_constraint(&new)?;
+    }
+
+    Ok((old, new))
+}
+
+/// Return a copy of the constraint moved to `lifecycle`, following the state machine
+fn apply_transition(
+    previous: &Constraint,
+    lifecycle: Lifecycle,
+) -> Result<Constraint, ConstraintError> {
+    if previous.is_archived() {
+        return Err(ConstraintError::Validation(format!(
+            "Constraint {} is archived; restore it before changing its lifecycle",
+            previous.id
+        )));
+    }
+
+    let mut constraint = previous.clone();
+    constraint
+        .update(ConstraintUpdate {
+            lifecycle: Some(lifecycle),
+            ..Default::default()
+        })
+        .map_err(|e| match e {
+            ConstraintError::Validation(message) => {
+                ConstraintError::Validation(format!("{}: {}", previous.id, message))
+            }
+            other => other,
+        })?;
+
+    Ok(constraint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::MemoryStore;
+
+    fn stored(store: &MemoryStore, text: &str, lifecycle: Lifecycle) -> Constraint {
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: text.to_string(),
+            author: "author".to_string(),
+            lifecycle,
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        constraint
+    }
+
+    #[test]
+    fn test_transition_constraint() {
+        let store = MemoryStore::new();
+        let constraint = stored(&store, "Hash passwords", Lifecycle::Draft);
+
+        // Drafts must be proposed before they can be accepted
+        assert!(
+            transition_constraint(&store, "reviewer", &constraint.id, Lifecycle::Accepted).is_err()
+        );
+
+        for lifecycle in [
+            Lifecycle::Proposed,
+            Lifecycle::Accepted,
+            Lifecycle::Deprecated,
+        ] {
+            let updated =
+                transition_constraint(&store, "reviewer", &constraint.id, lifecycle).unwrap();
+            assert_eq!(updated.lifecycle, lifecycle);
+        }
+
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].action, RevisionAction::Transitioned);
+        assert_eq!(
+            history[2].new.get("lifecycle"),
+            Some(&serde_json::Value::from("deprecated"))
+        );
+    }
+
+    #[test]
+    fn test_supersede_constraint() {
+        let store = MemoryStore::new();
+        let old = stored(&store, "Hash passwords with bcrypt", Lifecycle::Accepted);
+        let new = stored(&store, "Hash passwords with argon2id", Lifecycle::Accepted);
+
+        let (old, new) = supersede_constraint(&store, "reviewer", &old.id, &new.id).unwrap();
+        assert_eq!(old.lifecycle, Lifecycle::Superseded);
+        assert_eq!(new.supersedes, vec![old.id.clone()]);
+        assert_eq!(
+            store.read_constraint_by_id(&new.id).unwrap().supersedes,
+            vec![old.id.clone()]
+        );
+
+        // Superseded is final
+        assert!(supersede_constraint(&store, "reviewer", &old.id, &new.id).is_err());
+        assert!(transition_constraint(&store, "reviewer", &old.id, Lifecycle::Accepted).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Implementation of the 'links' command
+
+use serde::Serialize;
+
+use crate::cli::args::LinksArgs;
+use crate::core::error::ConstraintError;
+use crate::core::relation::Relation;
+use crate::storage::{self, incoming_relations, resolve_constraint, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// An outgoing edge together with whether its target still exists
+#[derive(Debug, Serialize)]
+struct LinkEntry {
+    #[serde(flatten)]
+    relation: Relation,
+
+    /// Whether the target matches no stored constraint
+    dangling: bool,
+}
+
+/// Both directions of a constraint's relationships
+#[derive(Debug, Serialize)]
+struct Links {
+    id: String,
+    outgoing: Vec<LinkEntry>,
+    incoming: Vec<Relation>,
+}
+
+/// Run the links command
+pub fn run(args: LinksArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let links = constraint_links(store.as_ref(), &args.id)?;
+
+    // Output results
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
+        println!("{}", serde_json::to_string_pretty(&links)?);
+    } else {
+        output_human(&links);
+    }
+
+    Ok(())
+}
+
+/// Collect the edges leaving and entering a constraint
+fn constraint_links(store: &dyn ConstraintStore, id: &str) -> Result<Links, ConstraintError> {
+    let constraint = resolve_constraint(store, id)?;
+
+    let mut outgoing = vec![];
+    for relation in constraint.relations() {
+        let dangling = match store.read_constraint_by_id(&relation.target) {
+            Ok(_) => false,
+            Err(ConstraintError::NotFound { .. }) => true,
+            Err(e) => return Err(e),
+        };
+        outgoing.push(LinkEntry { relation, dangling });
+    }
+
+    Ok(Links {
+        incoming: incoming_relations(store, &constraint.id)?,
+        id: constraint.id,
+        outgoing,
+    })
+}
+
+/// Output links in human-readable format
+fn output_human(links: &Links) {
+    if links.outgoing.is_empty() && links.incoming.is_empty() {
+        println!("{} has no links.", links.id);
+        return;
+    }
+
+    println!("Outgoing ({}):", links.outgoing.len());
+    for entry in &links.outgoing {
+        println!(
+            "  {} {} {}{}",
+            links.id,
+            entry.relation.kind,
+            entry.relation.target,
+            if entry.dangling { " (dangling)" } else { "" }
+        );
+    }
+
+    println!();
+    println!("Incoming ({}):", links.incoming.len());
+    for relation in &links.incoming {
+        println!("  {} {} {}", relation.source, relation.kind, links.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{Constraint, ConstraintParams, ConstraintType};
+    use crate::core::relation::RelationKind;
+    use crate::storage::MemoryStore;
+
+    #[test]
+    fn test_constraint_links() {
+        let store = MemoryStore::new();
+        let base = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            conflicts_with: vec!["nt-gone01".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let refined = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Use argon2id".to_string(),
+            author: "author".to_string(),
+            depends_on: vec![base.id.clone()],
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&base).unwrap();
+        store.write_constraint(&refined).unwrap();
+
+        let links = constraint_links(&store, &base.id).unwrap();
+        assert_eq!(links.outgoing.len(), 1);
+        assert!(links.outgoing[0].dangling);
+        assert_eq!(links.incoming.len(), 1);
+        assert_eq!(links.incoming[0].source, refined.id);
+        assert_eq!(links.incoming[0].kind, RelationKind::DependsOn);
+
+        let links = constraint_links(&store, &refined.id).unwrap();
+        assert!(!links.outgoing[0].dangling);
+        assert!(links.incoming.is_empty());
+    }
+}
@@ -1,24 +1,27 @@
 //! Implementation of the 'list' command
 
 use crate::cli::args::ListArgs;
-use crate::storage::jsonl::JsonlStorage;
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::relation::RelationKind;
+use crate::storage::{self, ConstraintStore};
 use crate::utils::workspace::Workspace;
 
 /// Run the list command
 pub fn run(args: ListArgs) -> Result<(), Box<dyn std::error::Error>> {
     // Discover workspace
     let workspace = Workspace::discover()?;
-    let storage = JsonlStorage::new(workspace.constraints_dir());
+    let store = storage::open(&workspace)?;
 
     // Get constraints
-    let constraints = if let Some(category) = &args.category {
-        storage.read_category_constraints(category)?
-    } else {
-        storage.read_all_constraints()?
-    };
+    let constraints = list_constraints(
+        store.as_ref(),
+        args.category.as_deref(),
+        args.include_archived,
+    )?;
 
     // Output results
-    if args.format == "json" {
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
         output_json(&constraints)?;
     } else {
         output_human(&constraints)?;
@@ -27,6 +30,23 @@ pub fn run(args: ListArgs) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Read the constraints selected by the list filters
+pub fn list_constraints(
+    store: &dyn ConstraintStore,
+    category: Option<&str>,
+    include_archived: bool,
+) -> Result<Vec<Constraint>, ConstraintError> {
+    let mut constraints = if let Some(category) = category {
+        store.read_category_constraints(category)?
+    } else {
+        store.read_all_constraints()?
+    };
+    if !include_archived {
+        constraints.retain(|c| !c.is_archived());
+    }
+    Ok(constraints)
+}
+
 /// Output constraints in JSON format
 fn output_json(
     constraints: &[crate::core::constraint::Constraint],
@@ -57,10 +77,11 @@ fn output_human(
         );
 
         println!(
-            "  Author: {} | Created: {} | Status: {}",
+            "  Author: {} | Created: {} | Status: {} | Lifecycle: {}",
             constraint.author,
             constraint.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
-            format_validation_status(&constraint.validation_status)
+            format_validation_status(&constraint.validation_status),
+            constraint.lifecycle
         );
 
         if !constraint.tags.is_empty() {
@@ -79,6 +100,20 @@ fn output_human(
             println!("  References: {}", constraint.references);
         }
 
+        for kind in RelationKind::ALL {
+            let targets = constraint.relation_targets(kind);
+            if !targets.is_empty() {
+                println!("  {}: {}", kind, targets.join(", "));
+            }
+        }
+
+        if let Some(archived_at) = constraint.archived_at {
+            println!(
+                "  Archived: {}",
+                archived_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+
         println!();
     }
 
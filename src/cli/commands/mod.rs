@@ -1,8 +1,22 @@
 //! CLI command implementations
 
 pub mod add;
+pub mod aliases;
+pub mod approve;
+pub mod audit;
 pub mod delete;
+pub mod graph;
+pub mod history;
+pub mod init;
+pub mod lifecycle;
+pub mod links;
 pub mod list;
+pub mod r#move;
 pub mod patch;
+pub mod reid;
+pub mod report;
+pub mod restore;
 pub mod search;
+pub mod sign;
+pub mod storage;
 pub mod validate;
@@ -0,0 +1,163 @@
+//! Implementation of the 'move' command
+
+use crate::cli::args::MoveArgs;
+use crate::cli::commands::add::{parse_constraint_type, validate_category};
+use crate::core::constraint::{Constraint, ConstraintUpdate};
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::storage::{self, resolve_constraint, ConstraintStore};
+use crate::utils::config::WorkspaceConfig;
+use crate::utils::workspace::Workspace;
+
+/// Run the move command
+pub fn run(args: MoveArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let actor = workspace.config().actor(args.actor.as_deref());
+    let constraint = move_constraint(store.as_ref(), workspace.config(), &actor, &args)?;
+
+    // Output result
+    println!(
+        "Constraint {} is now {} [{}].",
+        constraint.id,
+        constraint.r#type.as_str(),
+        constraint.category
+    );
+
+    Ok(())
+}
+
+/// Change the category and/or type of a stored constraint, keeping its ID
+fn move_constraint(
+    store: &dyn ConstraintStore,
+    config: &WorkspaceConfig,
+    actor: &str,
+    args: &MoveArgs,
+) -> Result<Constraint, ConstraintError> {
+    if args.category.is_none() && args.r#type.is_none() {
+        return Err(ConstraintError::Validation(
+            "Nothing to change: pass --category and/or --type".to_string(),
+        ));
+    }
+
+    let previous = resolve_constraint(store, &args.id)?;
+    if previous.is_archived() {
+        return Err(ConstraintError::Validation(format!(
+            "Constraint {} is archived; restore it before moving",
+            previous.id
+        )));
+    }
+
+    let mut update = ConstraintUpdate::default();
+
+    if let Some(category) = &args.category {
+        validate_category(category)?;
+        config.check_category(category)?;
+        update.category = Some(category.clone());
+    }
+
+    if let Some(type_str) = &args.r#type {
+        update.r#type = Some(parse_constraint_type(type_str)?);
+    }
+
+    let mut constraint = previous.clone();
+    constraint.update(update)?;
+
+    let revision = Revision::between(
+        RevisionAction::Moved,
+        actor,
+        Some(&previous),
+        Some(&constraint),
+    )?;
+    if revision.is_empty() {
+        return Ok(previous);
+    }
+
+    store.append_revision(&revision)?;
+    store.relocate_constraint(&constraint, &previous.category)?;
+
+    Ok(constraint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::MemoryStore;
+
+    fn move_args(id: &str, category: Option<&str>, r#type: Option<&str>) -> MoveArgs {
+        MoveArgs {
+            id: id.to_string(),
+            category: category.map(str::to_string),
+            r#type: r#type.map(str::to_string),
+            actor: None,
+        }
+    }
+
+    #[test]
+    fn test_move_and_retype() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Should,
+            category: "testing".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        let config = WorkspaceConfig::default();
+
+        let moved = move_constraint(
+            &store,
+            &config,
+            "reviewer",
+            &move_args(&constraint.id, Some("security"), Some("must")),
+        )
+        .unwrap();
+        assert_eq!(moved.id, constraint.id);
+
+        let stored = store.read_constraint_by_id(&constraint.id).unwrap();
+        assert_eq!(stored.category, "security");
+        assert_eq!(stored.r#type, ConstraintType::Must);
+        assert!(store
+            .read_category_constraints("testing")
+            .unwrap()
+            .is_empty());
+
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, RevisionAction::Moved);
+        assert_eq!(history[0].changes().len(), 2);
+    }
+
+    #[test]
+    fn test_move_rejects_bad_input() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        let config = WorkspaceConfig {
+            allowed_categories: vec!["security".to_string()],
+            ..WorkspaceConfig::default()
+        };
+
+        for args in [
+            move_args(&constraint.id, None, None),
+            move_args(&constraint.id, Some("testing"), None),
+            move_args(&constraint.id, Some("Bad Category"), None),
+            move_args(&constraint.id, None, Some("OPTIONAL")),
+        ] {
+            assert!(move_constraint(&store, &config, "reviewer", &args).is_err());
+        }
+        assert!(store.read_history(&constraint.id).unwrap().is_empty());
+    }
+}
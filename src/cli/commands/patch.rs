@@ -1,19 +1,54 @@
 //! Implementation of the 'patch' command
 
 use crate::cli::args::PatchArgs;
-use crate::core::constraint::ConstraintUpdate;
-use crate::storage::jsonl::JsonlStorage;
+use crate::cli::commands::add::parse_verification;
+use crate::core::constraint::{Constraint, ConstraintUpdate};
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::storage::{
+    self, check_relations, resolve_constraint, resolve_relation_targets, ConstraintStore,
+};
 use crate::utils::workspace::Workspace;
 
 /// Run the patch command
 pub fn run(args: PatchArgs) -> Result<(), Box<dyn std::error::Error>> {
     // Discover workspace
     let workspace = Workspace::discover()?;
-    let storage = JsonlStorage::new(workspace.constraints_dir());
+    let store = storage::open(&workspace)?;
 
+    let actor = workspace.config().actor(args.actor.as_deref());
+    let (constraint, lapsed) = patch_constraint(store.as_ref(), &actor, args)?;
+
+    // Output result
+    println!("Constraint {} updated successfully.", constraint.id);
+    if lapsed > 0 {
+        println!(
+            "Note: {} approval(s) no longer apply to the changed content.",
+            lapsed
+        );
+    }
+
+    Ok(())
+}
+
+/// Apply the requested updates to a stored constraint and record the revision
+///
+/// Returns the patched constraint and the number of approvals the change
+/// invalidated.
+fn patch_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    args: PatchArgs,
+) -> Result<(Constraint, usize), ConstraintError> {
     // Read existing constraint
-    let constraint_id = args.id.clone();
-    let mut constraint = storage.read_constraint_by_id(&constraint_id)?;
+    let mut constraint = resolve_constraint(store, &args.id)?;
+    if constraint.is_archived() {
+        return Err(ConstraintError::Validation(format!(
+            "Constraint {} is archived; restore it before patching",
+            constraint.id
+        )));
+    }
+    let previous = constraint.clone();
 
     // Build update from provided arguments
     let mut update = ConstraintUpdate::default();
@@ -34,18 +69,205 @@ pub fn run(args: PatchArgs) -> Result<(), Box<dyn std::error::Error>> {
         update.references = Some(references);
     }
 
-    if let Some(verification) = args.verification {
-        update.verification = Some(Some(verification));
+    if args.verification.is_some() || args.verification_spec.is_some() {
+        update.verification = Some(parse_verification(
+            args.verification,
+            args.verification_spec.as_deref(),
+        )?);
+    }
+
+    if let Some(depends_on) = args.depends_on {
+        update.depends_on = Some(resolve_relation_targets(store, &depends_on)?);
+    }
+
+    if let Some(supersedes) = args.supersedes {
+        update.supersedes = Some(resolve_relation_targets(store, &supersedes)?);
+    }
+
+    if let Some(conflicts_with) = args.conflicts_with {
+        update.conflicts_with = Some(resolve_relation_targets(store, &conflicts_with)?);
     }
 
     // Apply the update
     constraint.update(update)?;
+    check_relations(store, &constraint)?;
 
-    // Write back to storage
-    storage.write_constraint(&constraint)?;
+    // Record what changed, skipping no-op patches, then write back to storage
+    let revision = Revision::between(
+        RevisionAction::Updated,
+        actor,
+        Some(&previous),
+        Some(&constraint),
+    )?;
+    if !revision.is_empty() {
+        store.append_revision(&revision)?;
+    }
+    store.write_constraint(&constraint)?;
 
-    // Output result
-    println!("Constraint {} updated successfully.", constraint.id);
+    // Reverting to earlier content can bring older approvals back into force
+    let lapsed = previous
+        .current_approvals()
+        .len()
+        .saturating_sub(constraint.current_approvals().len());
+    Ok((constraint, lapsed))
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::{AuditLog, AuditedStore, MemoryStore};
+
+    fn patch_args(id: &str) -> PatchArgs {
+        PatchArgs {
+            id: id.to_string(),
+            text: None,
+            tags: None,
+            priority: None,
+            references: None,
+            verification: None,
+            verification_spec: None,
+            depends_on: None,
+            supersedes: None,
+            conflicts_with: None,
+            actor: None,
+        }
+    }
+
+    #[test]
+    fn test_patch_constraint() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Original requirement".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        let mut args = patch_args(&constraint.id);
+        args.text = Some("Updated requirement".to_string());
+        args.priority = Some("P1".to_string());
+        patch_constraint(&store, "reviewer", args).unwrap();
+
+        let stored = store.read_constraint_by_id(&constraint.id).unwrap();
+        assert_eq!(stored.text, "Updated requirement");
+        assert_eq!(stored.priority.as_deref(), Some("P1"));
+
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].actor, "reviewer");
+        assert_eq!(history[0].changes().len(), 2);
+
+        // A patch that changes nothing leaves no revision
+        patch_constraint(&store, "reviewer", patch_args(&constraint.id)).unwrap();
+        assert_eq!(store.read_history(&constraint.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_patch_refused_without_audit_log() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Original requirement".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let inner = MemoryStore::new();
+        inner.write_constraint(&constraint).unwrap();
+
+        // The log path is a directory, so no entry can be appended
+        let log_path = temp_dir.path().join("audit.jsonl");
+        std::fs::create_dir(&log_path).unwrap();
+        let store = AuditedStore::new(Box::new(inner), AuditLog::new(log_path), "ci".to_string());
+
+        let mut args = patch_args(&constraint.id);
+        args.text = Some("Updated requirement".to_string());
+        assert!(patch_constraint(&store, "reviewer", args).is_err());
+
+        // Neither the record nor its history changed
+        let stored = store.read_constraint_by_id(&constraint.id).unwrap();
+        assert_eq!(stored.text, "Original requirement");
+        assert!(store.read_history(&constraint.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_patch_invalidates_approvals() {
+        let store = MemoryStore::new();
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Original requirement".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        constraint.approve("reviewer", None).unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        // A no-op patch keeps the approval
+        let (patched, lapsed) =
+            patch_constraint(&store, "author", patch_args(&constraint.id)).unwrap();
+        assert_eq!(patched.current_approvals().len(), 1);
+        assert_eq!(lapsed, 0);
+
+        let mut args = patch_args(&constraint.id);
+        args.text = Some("Updated requirement".to_string());
+        let (_, lapsed) = patch_constraint(&store, "author", args).unwrap();
+        assert_eq!(lapsed, 1);
+
+        let stored = store.read_constraint_by_id(&constraint.id).unwrap();
+        assert!(stored.current_approvals().is_empty());
+        assert_eq!(stored.approvals.len(), 1);
+    }
+
+    #[test]
+    fn test_patch_missing_constraint() {
+        let store = MemoryStore::new();
+        let result = patch_constraint(&store, "reviewer", patch_args("nt-000000"));
+        assert!(matches!(result, Err(ConstraintError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_patch_rejects_dependency_cycles() {
+        let store = MemoryStore::new();
+        let first = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let second = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Use argon2id".to_string(),
+            author: "author".to_string(),
+            depends_on: vec![first.id.clone()],
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&first).unwrap();
+        store.write_constraint(&second).unwrap();
+
+        let mut args = patch_args(&first.id);
+        args.depends_on = Some(vec![second.id.clone()]);
+        match patch_constraint(&store, "reviewer", args) {
+            Err(ConstraintError::Validation(message)) => {
+                assert!(message.starts_with("Dependency cycle"));
+            }
+            other => panic!("expected a cycle error, got {:?}", other),
+        }
+
+        // Clearing with an empty value is allowed
+        let mut args = patch_args(&second.id);
+        args.depends_on = Some(vec![String::new()]);
+        let (patched, _) = patch_constraint(&store, "reviewer", args).unwrap();
+        assert!(patched.depends_on.is_empty());
+    }
 }
@@ -0,0 +1,368 @@
+//! Implementation of the 'reid' command
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use crate::cli::args::ReidArgs;
+use crate::cli::commands::add::MAX_ID_ATTEMPTS;
+use crate::core::alias::Alias;
+use crate::core::constraint::{Constraint, ConstraintType};
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::storage::{self, id_in_use, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// A constraint still identified by the legacy algorithm, and its new ID
+struct Reassignment {
+    constraint: Constraint,
+    new_id: String,
+}
+
+/// Run the reid command
+pub fn run(args: ReidArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let plan = plan_reid(store.as_ref(), &workspace.config().id_prefix)?;
+
+    if plan.is_empty() {
+        println!("All constraint IDs are up to date.");
+        return Ok(());
+    }
+
+    for reassignment in &plan {
+        println!("{} -> {}", reassignment.constraint.id, reassignment.new_id);
+    }
+
+    if args.dry_run {
+        println!("{} constraint(s) would be re-identified.", plan.len());
+    } else {
+        let actor = workspace.config().actor(args.actor.as_deref());
+        apply_reid(store.as_ref(), &actor, &plan)?;
+        println!(
+            "{} constraint(s) re-identified; old IDs remain as aliases.",
+            plan.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Work out the new ID of every constraint still carrying a legacy ID
+///
+/// Only IDs the pre-SHA-256 algorithm produced for the content a constraint
+/// was created with are reassigned; IDs chosen with `--id`, and IDs kept
+/// through later patches, moves and retypes, are left alone. Each legacy
+/// constraint walks its candidate sequence like `add` does and takes the
+/// first candidate not held by a constraint, an alias or an earlier
+/// reassignment.
+fn plan_reid(
+    store: &dyn ConstraintStore,
+    prefix: &str,
+) -> Result<Vec<Reassignment>, ConstraintError> {
+    let aliases = store.read_aliases()?;
+    let mut constraints = store.read_all_constraints()?;
+    constraints.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut claimed = HashSet::new();
+    let mut plan = vec![];
+
+    for constraint in constraints {
+        let (r#type, category, text) = created_content(store, &constraint)?;
+        if constraint.id != legacy_id(&text, &category, &r#type) {
+            continue;
+        }
+
+        let mut new_id = None;
+
+        for attempt in 0..MAX_ID_ATTEMPTS {
+            let candidate = Constraint::generate_alternate_id(
+                prefix,
+                &constraint.text,
+                &constraint.category,
+                &constraint.r#type,
+                attempt,
+            );
+
+            if !claimed.contains(&candidate) && !id_in_use(store, &aliases, &candidate)? {
+                new_id = Some(candidate);
+                break;
+            }
+        }
+
+        if let Some(new_id) = new_id {
+            claimed.insert(new_id.clone());
+            plan.push(Reassignment { constraint, new_id });
+        }
+    }
+
+    Ok(plan)
+}
+
+/// The type, category and text a constraint was created with
+///
+/// Read from its `Created` revision; records without one fall back to their
+/// current content.
+fn created_content(
+    store: &dyn ConstraintStore,
+    constraint: &Constraint,
+) -> Result<(ConstraintType, String, String), ConstraintError> {
+    let mut content = (
+        constraint.r#type,
+        constraint.category.clone(),
+        constraint.text.clone(),
+    );
+
+    let history = store.read_history(&constraint.id)?;
+    let Some(created) = history
+        .iter()
+        .find(|revision| revision.action == RevisionAction::Created)
+    else {
+        return Ok(content);
+    };
+
+    if let Some(r#type) = created.new.get("type") {
+        content.0 = serde_json::from_value(r#type.clone())?;
+    }
+    if let Some(Value::String(category)) = created.new.get("category") {
+        content.1 = category.clone();
+    }
+    if let Some(Value::String(text)) = created.new.get("text") {
+        content.2 = text.clone();
+    }
+
+    Ok(content)
+}
+
+/// The ID the pre-SHA-256 algorithm derived for some content
+///
+/// That algorithm fed `DefaultHasher` and kept the first six hex digits of
+/// the result; it is reproduced here only to recognize the IDs it issued.
+fn legacy_id(text: &str, category: &str, r#type: &ConstraintType) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    category.hash(&mut hasher);
+    format!("{:?}", r#type).hash(&mut hasher);
+
+    let suffix: String = format!("{:x}", hasher.finish()).chars().take(6).collect();
+    format!("nt-{}", suffix)
+}
+
+/// Rewrite each planned constraint under its new ID and alias the old one
+///
+/// Relationships pointing at a rewritten ID are updated to the new one.
+fn apply_reid(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    plan: &[Reassignment],
+) -> Result<(), ConstraintError> {
+    let renamed_ids: HashMap<String, String> = plan
+        .iter()
+        .map(|r| (r.constraint.id.clone(), r.new_id.clone()))
+        .collect();
+
+    for Reassignment { constraint, new_id } in plan {
+        let mut renamed = constraint.clone();
+        renamed.id = new_id.clone();
+        renamed.retarget_relations(&renamed_ids);
+
+        store.append_revision(&Revision::between(
+            RevisionAction::Reidentified,
+            actor,
+            Some(constraint),
+            Some(&renamed),
+        )?)?;
+        store.write_constraint(&renamed)?;
+        store.delete_constraint(&constraint.category, &constraint.id)?;
+        store.write_alias(&Alias::new(&constraint.id, new_id))?;
+    }
+
+    // Constraints that kept their ID may still point at rewritten ones
+    for previous in store.read_all_constraints()? {
+        if renamed_ids.values().any(|id| *id == previous.id) {
+            continue;
+        }
+
+        let mut constraint = previous.clone();
+        if constraint.retarget_relations(&renamed_ids) {
+            store.append_revision(&Revision::between(
+                RevisionAction::Updated,
+                actor,
+                Some(&previous),
+                Some(&constraint),
+            )?)?;
+            store.write_constraint(&constraint)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintUpdate};
+    use crate::storage::MemoryStore;
+
+    fn constraint(id: Option<&str>, text: &str) -> Constraint {
+        Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: text.to_string(),
+            author: "author".to_string(),
+            id: id.map(str::to_string),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    /// A constraint as the pre-SHA-256 algorithm would have identified it
+    fn legacy(text: &str) -> Constraint {
+        constraint(
+            Some(&legacy_id(text, "security", &ConstraintType::Must)),
+            text,
+        )
+    }
+
+    #[test]
+    fn test_reid_rewrites_and_aliases() {
+        let store = MemoryStore::new();
+        let current = constraint(None, "Hash passwords");
+        let legacy = legacy("Rotate keys yearly");
+        let chosen = constraint(Some("nt-abc123"), "Review access quarterly");
+        store.write_constraint(&current).unwrap();
+        store.write_constraint(&legacy).unwrap();
+        store.write_constraint(&chosen).unwrap();
+
+        // Only the legacy ID is reassigned; an ID picked with --id stays
+        let plan = plan_reid(&store, "nt").unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].constraint.id, legacy.id);
+        let new_id = plan[0].new_id.clone();
+        assert_eq!(new_id, constraint(None, "Rotate keys yearly").id);
+
+        apply_reid(&store, "maintainer", &plan).unwrap();
+
+        // The old ID still resolves, through an alias
+        assert_eq!(store.read_constraint_by_id(&legacy.id).unwrap().id, new_id);
+        assert_eq!(store.read_all_constraints().unwrap().len(), 3);
+
+        let history = store.read_history(&new_id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, RevisionAction::Reidentified);
+        assert_eq!(history[0].changes()[0].field, "id");
+
+        // Running again finds nothing left to do
+        assert!(plan_reid(&store, "nt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reid_keeps_patched_ids() {
+        let store = MemoryStore::new();
+        let created = constraint(None, "Hash passwords");
+        store.write_constraint(&created).unwrap();
+        store
+            .append_revision(
+                &Revision::between(RevisionAction::Created, "author", None, Some(&created))
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let mut patched = created.clone();
+        patched
+            .update(ConstraintUpdate {
+                text: Some("Hash passwords with argon2id".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        store.write_constraint(&patched).unwrap();
+        store
+            .append_revision(
+                &Revision::between(
+                    RevisionAction::Updated,
+                    "author",
+                    Some(&created),
+                    Some(&patched),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        // Neither run touches an ID issued by the current algorithm
+        for _ in 0..2 {
+            let plan = plan_reid(&store, "nt").unwrap();
+            assert!(plan.is_empty());
+            apply_reid(&store, "maintainer", &plan).unwrap();
+        }
+        assert_eq!(store.read_all_constraints().unwrap()[0].id, created.id);
+        assert!(store.read_aliases().unwrap().is_empty());
+
+        // A legacy ID is recognized from the content it was created with
+        let original = legacy("Rotate keys yearly");
+        let mut edited = original.clone();
+        edited.text = "Rotate keys monthly".to_string();
+        store.write_constraint(&edited).unwrap();
+        store
+            .append_revision(
+                &Revision::between(RevisionAction::Created, "author", None, Some(&original))
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let plan = plan_reid(&store, "nt").unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].constraint.id, original.id);
+        apply_reid(&store, "maintainer", &plan).unwrap();
+        assert!(plan_reid(&store, "nt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reid_retargets_relations() {
+        let store = MemoryStore::new();
+        let legacy = legacy("Hash passwords");
+        let mut dependent = constraint(None, "Use argon2id");
+        dependent.depends_on = vec![legacy.id.clone()];
+        store.write_constraint(&legacy).unwrap();
+        store.write_constraint(&dependent).unwrap();
+
+        let plan = plan_reid(&store, "nt").unwrap();
+        assert_eq!(plan.len(), 1);
+        apply_reid(&store, "maintainer", &plan).unwrap();
+
+        let stored = store.read_constraint_by_id(&dependent.id).unwrap();
+        assert_eq!(stored.depends_on, vec![plan[0].new_id.clone()]);
+        assert_eq!(
+            store.read_history(&dependent.id).unwrap()[0].action,
+            RevisionAction::Updated
+        );
+    }
+
+    #[test]
+    fn test_reid_skips_taken_ids() {
+        let store = MemoryStore::new();
+        let natural = constraint(None, "Hash passwords").id;
+        store.write_constraint(&legacy("Hash passwords")).unwrap();
+        store
+            .write_alias(&Alias::new(&natural, "nt-gone01"))
+            .unwrap();
+
+        let plan = plan_reid(&store, "nt").unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_ne!(plan[0].new_id, natural);
+        assert_eq!(
+            plan[0].new_id,
+            Constraint::generate_alternate_id(
+                "nt",
+                "Hash passwords",
+                "security",
+                &ConstraintType::Must,
+                1
+            )
+        );
+    }
+}
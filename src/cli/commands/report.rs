@@ -0,0 +1,257 @@
+//! Implementation of the 'report' command
+
+use crate::cli::args::ReportArgs;
+use crate::core::error::ConstraintError;
+use crate::storage::{compare_runs, RunLog, RunStatus, StatusChange, ValidationRun};
+use crate::utils::workspace::Workspace;
+
+/// Every status, in the order summaries list them
+const STATUSES: [RunStatus; 6] = [
+    RunStatus::Passed,
+    RunStatus::Failed,
+    RunStatus::TimedOut,
+    RunStatus::Skipped,
+    RunStatus::Valid,
+    RunStatus::Invalid,
+];
+
+/// Run the report command
+pub fn run(args: ReportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let log = RunLog::new(workspace.runs_dir());
+    let json = workspace.config().output_format(args.format.as_deref()) == "json";
+
+    if args.list {
+        let runs = log.list()?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&runs)?);
+        } else {
+            output_list(&runs);
+        }
+        return Ok(());
+    }
+
+    let compare = args.compare.as_ref().map(Option::as_deref);
+    let Some((run, base)) = select_runs(&log, args.run.as_deref(), compare)? else {
+        println!("No validation runs recorded yet (run `constraint validate` first).");
+        return Ok(());
+    };
+
+    // Output results
+    match base {
+        Some(base) => {
+            let changes = compare_runs(&base, &run);
+            if json {
+                let report = serde_json::json!({
+                    "base": base.id,
+                    "run": run.id,
+                    "changes": changes,
+                });
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                output_changes(&base, &run, &changes);
+            }
+        }
+        None if json => println!("{}", serde_json::to_string_pretty(&run)?),
+        None => output_run(&run, args.verbose),
+    }
+
+    Ok(())
+}
+
+/// Pick the run to show and, when comparing, the earlier run to compare it with
+///
+/// `compare` is `Some(None)` to compare with the run recorded before the
+/// shown one. Returns `None` when no runs have been recorded.
+fn select_runs(
+    log: &RunLog,
+    run: Option<&str>,
+    compare: Option<Option<&str>>,
+) -> Result<Option<(ValidationRun, Option<ValidationRun>)>, ConstraintError> {
+    let runs = log.list()?;
+    let run = match run {
+        Some(reference) => log.find(reference)?,
+        None => match runs.last() {
+            Some(latest) => latest.clone(),
+            None => return Ok(None),
+        },
+    };
+
+    let base = match compare {
+        None => None,
+        Some(Some(reference)) => Some(log.find(reference)?),
+        Some(None) => {
+            let position = runs.iter().position(|r| r.id == run.id).unwrap_or(0);
+            match position.checked_sub(1) {
+                Some(previous) => Some(runs[previous].clone()),
+                None => {
+                    return Err(ConstraintError::Validation(format!(
+                        "Run {} is the first recorded run; there is nothing to compare it with",
+                        run.id
+                    )))
+                }
+            }
+        }
+    };
+
+    Ok(Some((run, base)))
+}
+
+/// Output the recorded runs in human-readable format
+fn output_list(runs: &[ValidationRun]) {
+    if runs.is_empty() {
+        println!("No validation runs recorded yet.");
+        return;
+    }
+
+    println!("{} recorded run(s):", runs.len());
+    for run in runs {
+        println!(
+            "  {} {:<18}  {}  {}  {}  {} checked",
+            if run.passed { "✅" } else { "❌" },
+            run.id,
+            run.started_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            run.git_revision
+                .as_deref()
+                .map_or("-", |rev| &rev[..rev.len().min(12)]),
+            if run.execute { "execute" } else { "structure" },
+            run.results.len()
+        );
+    }
+}
+
+/// Output one run in human-readable format
+fn output_run(run: &ValidationRun, verbose: bool) {
+    println!("Run {}", run.id);
+    println!(
+        "  Started: {}",
+        run.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if let Some(revision) = &run.git_revision {
+        println!("  Revision: {}", revision);
+    }
+    println!(
+        "  Checks: {}",
+        if run.execute {
+            "verification commands"
+        } else {
+            "structure only"
+        }
+    );
+    println!(
+        "  Result: {}",
+        if run.passed {
+            "✅ passed"
+        } else {
+            "❌ failed"
+        }
+    );
+    println!();
+
+    for result in &run.results {
+        println!(
+            "{} {} - {}{}",
+            result.status.icon(),
+            result.constraint_id,
+            result.status.label(),
+            if result.cached { " (cached)" } else { "" }
+        );
+        println!("   {}", result.text);
+        if verbose {
+            if let Some(output) = &result.output {
+                println!("   Output: {}", output);
+            }
+        }
+        if let Some(error) = &result.error {
+            println!("   Error: {}", error);
+        }
+        println!("   Duration: {}ms", result.duration_ms);
+        println!();
+    }
+
+    println!("Summary:");
+    for status in STATUSES {
+        let count = run.count(status);
+        if count > 0 {
+            println!("  {} {}: {}", status.icon(), status.label(), count);
+        }
+    }
+    println!("  📊 Total: {}", run.results.len());
+}
+
+/// Output the status changes between two runs in human-readable format
+fn output_changes(base: &ValidationRun, run: &ValidationRun, changes: &[StatusChange]) {
+    println!("Changes from run {} to run {}:", base.id, run.id);
+    println!();
+
+    if changes.is_empty() {
+        println!("No constraint changed status.");
+        return;
+    }
+
+    let label = |status: Option<RunStatus>| status.map_or("(not checked)", |s| s.label());
+    for change in changes {
+        let icon = match change.after {
+            Some(status) => status.icon(),
+            None => "➖",
+        };
+        println!(
+            "{} {}: {} -> {}",
+            icon,
+            change.constraint_id,
+            label(change.before),
+            label(change.after)
+        );
+        println!("   {}", change.text);
+    }
+
+    let newly_failing = changes
+        .iter()
+        .filter(|c| c.after.is_some_and(|s| s.is_failure()))
+        .filter(|c| !c.before.is_some_and(|s| s.is_failure()))
+        .count();
+    println!();
+    println!("{} constraint(s) changed status.", changes.len());
+    if newly_failing > 0 {
+        println!("❌ {} constraint(s) started failing.", newly_failing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_select_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = RunLog::new(temp_dir.path().join("runs"));
+        assert!(select_runs(&log, None, None).unwrap().is_none());
+
+        let mut first = ValidationRun::new(None, true);
+        log.save(&mut first, |_| Ok(())).unwrap();
+
+        // A single run cannot be compared with the one before it
+        assert!(select_runs(&log, None, Some(None)).is_err());
+
+        let mut second = ValidationRun::new(None, false);
+        log.save(&mut second, |_| Ok(())).unwrap();
+
+        // The latest run is shown by default, compared with the previous one
+        let (run, base) = select_runs(&log, None, Some(None)).unwrap().unwrap();
+        assert_eq!(run.id, second.id);
+        assert_eq!(base.map(|b| b.id), Some(first.id.clone()));
+
+        // Both sides can be chosen explicitly
+        let (run, base) = select_runs(&log, Some(&first.id), Some(Some(&second.id)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.id, first.id);
+        assert_eq!(base.map(|b| b.id), Some(second.id));
+
+        let (run, base) = select_runs(&log, Some(&first.id), None).unwrap().unwrap();
+        assert_eq!(run.id, first.id);
+        assert!(base.is_none());
+    }
+}
@@ -0,0 +1,85 @@
+//! Implementation of the 'restore' command
+
+use crate::cli::args::RestoreArgs;
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::history::{Revision, RevisionAction};
+use crate::storage::{self, resolve_constraint, ConstraintStore};
+use crate::utils::workspace::Workspace;
+
+/// Run the restore command
+pub fn run(args: RestoreArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let store = storage::open(&workspace)?;
+
+    let actor = workspace.config().actor(args.actor.as_deref());
+    let constraint = restore_constraint(store.as_ref(), &actor, &args.id)?;
+
+    // Output result
+    println!("Constraint {} restored.", constraint.id);
+
+    Ok(())
+}
+
+/// Un-archive a constraint and record the revision
+fn restore_constraint(
+    store: &dyn ConstraintStore,
+    actor: &str,
+    id: &str,
+) -> Result<Constraint, ConstraintError> {
+    let previous = resolve_constraint(store, id)?;
+
+    let mut constraint = previous.clone();
+    constraint.restore()?;
+
+    store.append_revision(&Revision::between(
+        RevisionAction::Restored,
+        actor,
+        Some(&previous),
+        Some(&constraint),
+    )?)?;
+    store.write_constraint(&constraint)?;
+
+    Ok(constraint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::MemoryStore;
+
+    #[test]
+    fn test_restore_constraint() {
+        let store = MemoryStore::new();
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Test constraint".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        // Restoring a live constraint is refused
+        assert!(matches!(
+            restore_constraint(&store, "author", &constraint.id),
+            Err(ConstraintError::Validation(_))
+        ));
+
+        constraint.archive().unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        restore_constraint(&store, "author", &constraint.id).unwrap();
+        assert!(!store
+            .read_constraint_by_id(&constraint.id)
+            .unwrap()
+            .is_archived());
+
+        let history = store.read_history(&constraint.id).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, RevisionAction::Restored);
+    }
+}
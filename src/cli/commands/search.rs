@@ -1,20 +1,24 @@
 //! Implementation of the 'search' command
 
 use crate::cli::args::SearchArgs;
-use crate::storage::jsonl::JsonlStorage;
+use crate::core::relation::RelationKind;
+use crate::storage;
 use crate::utils::workspace::Workspace;
 
 /// Run the search command
 pub fn run(args: SearchArgs) -> Result<(), Box<dyn std::error::Error>> {
     // Discover workspace
     let workspace = Workspace::discover()?;
-    let storage = JsonlStorage::new(workspace.constraints_dir());
+    let store = storage::open(&workspace)?;
 
     // Search constraints
-    let constraints = storage.search_constraints(&args.query, args.category.as_deref())?;
+    let mut constraints = store.search_constraints(&args.query, args.category.as_deref())?;
+    if !args.include_archived {
+        constraints.retain(|c| !c.is_archived());
+    }
 
     // Output results
-    if args.format == "json" {
+    if workspace.config().output_format(args.format.as_deref()) == "json" {
         output_json(&constraints)?;
     } else {
         output_human(&constraints, &args.query)?;
@@ -58,10 +62,11 @@ fn output_human(
         );
 
         println!(
-            "  Author: {} | Created: {} | Status: {}",
+            "  Author: {} | Created: {} | Status: {} | Lifecycle: {}",
             constraint.author,
             constraint.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
-            format_validation_status(&constraint.validation_status)
+            format_validation_status(&constraint.validation_status),
+            constraint.lifecycle
         );
 
         if !constraint.tags.is_empty() {
@@ -80,6 +85,20 @@ fn output_human(
             println!("  References: {}", constraint.references);
         }
 
+        for kind in RelationKind::ALL {
+            let targets = constraint.relation_targets(kind);
+            if !targets.is_empty() {
+                println!("  {}: {}", kind, targets.join(", "));
+            }
+        }
+
+        if let Some(archived_at) = constraint.archived_at {
+            println!(
+                "  Archived: {}",
+                archived_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+        }
+
         println!();
     }
 
@@ -0,0 +1,60 @@
+//! Implementation of the 'storage' command
+
+use crate::cli::args::{ConvertArgs, StorageArgs, StorageCommands};
+use crate::storage::AuditLog;
+use crate::utils::config::{JsonlLayout, StorageBackend};
+use crate::utils::workspace::Workspace;
+
+/// Run the storage command
+pub fn run(args: StorageArgs) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        StorageCommands::Convert(args) => convert(args),
+    }
+}
+
+/// Convert the workspace to another storage backend or layout
+fn convert(args: ConvertArgs) -> Result<(), Box<dyn std::error::Error>> {
+    // Discover workspace
+    let workspace = Workspace::discover()?;
+    let source_storage = workspace.config().storage.clone();
+
+    let mut target_storage = source_storage.clone();
+    target_storage.backend = args.to.parse::<StorageBackend>()?;
+    if let Some(layout) = &args.layout {
+        target_storage.layout = layout.parse::<JsonlLayout>()?;
+    }
+
+    // Layout only matters for jsonl, so compare the effective storage description
+    if source_storage.to_string() == target_storage.to_string() {
+        println!("Workspace already uses {} storage.", target_storage);
+        return Ok(());
+    }
+
+    let source = crate::storage::open_backend(&workspace, &source_storage)?;
+    let target = crate::storage::open_backend(&workspace, &target_storage)?;
+    let count = crate::storage::convert(source.as_ref(), target.as_ref(), args.force)?;
+
+    // Record the conversion before switching, so an unlogged one never takes effect
+    AuditLog::new(workspace.audit_path()).append(
+        &workspace.config().actor(None),
+        "converted",
+        None,
+        serde_json::json!({
+            "source": source_storage.to_string(),
+            "target": target_storage.to_string(),
+            "constraints": count,
+        }),
+    )?;
+
+    // Point the workspace at the new storage; the old data is left in place
+    let mut config = workspace.config().clone();
+    config.storage = target_storage.clone();
+    config.save(&workspace.config_path())?;
+
+    println!(
+        "Converted {} constraint(s) from {} to {}.",
+        count, source_storage, target_storage
+    );
+
+    Ok(())
+}
@@ -1,67 +1,378 @@
 //! Implementation of the 'validate' command
 
 use crate::cli::args::ValidateArgs;
-use crate::core::constraint::Constraint;
+use crate::core::constraint::{Constraint, ConstraintType};
 use crate::core::error::ConstraintError;
-use crate::storage::jsonl::JsonlStorage;
+use crate::core::lifecycle::Lifecycle;
+use crate::core::verification::VerificationSpec;
+use crate::storage::{
+    self, check_relations, fingerprint, resolve_constraint, AuditLog, CachedVerification,
+    ConstraintStore, RunLog, RunResult, RunStatus, ValidationRun, VerificationCache,
+};
+use crate::utils::config::ApprovalConfig;
+use crate::utils::junit::{self, TestCase, TestOutcome, TestSuite};
+use crate::utils::process::run_with_timeout;
 use crate::utils::workspace::Workspace;
-use std::process::Command;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 
 /// Validation result for a single constraint
 #[derive(Debug)]
 struct ValidationResult {
     constraint_id: String,
+    constraint_type: ConstraintType,
+    category: String,
     constraint_text: String,
-    status: ValidationStatus,
+    lifecycle: Lifecycle,
+    status: RunStatus,
     output: Option<String>,
     error: Option<String>,
     duration_ms: u128,
+    /// Reused from an earlier run with unchanged inputs
+    cached: bool,
+    approvals: usize,
+    required_approvals: u32,
 }
 
-/// Status of constraint validation
+impl ValidationResult {
+    /// Combine a check outcome with the constraint's details
+    fn new(constraint: &Constraint, outcome: CheckOutcome, required_approvals: u32) -> Self {
+        Self {
+            constraint_id: constraint.id.clone(),
+            constraint_type: constraint.r#type,
+            category: constraint.category.clone(),
+            constraint_text: constraint.text.clone(),
+            lifecycle: constraint.lifecycle,
+            status: outcome.status,
+            output: outcome.output,
+            error: outcome.error,
+            duration_ms: outcome.duration_ms,
+            cached: outcome.cached,
+            approvals: constraint.current_approvals().len(),
+            required_approvals,
+        }
+    }
+
+    /// The result as stored in the run log
+    fn to_run_result(&self) -> RunResult {
+        RunResult {
+            constraint_id: self.constraint_id.clone(),
+            r#type: self.constraint_type,
+            category: self.category.clone(),
+            text: self.constraint_text.clone(),
+            status: self.status,
+            output: self.output.clone(),
+            error: self.error.clone(),
+            duration_ms: self.duration_ms as u64,
+            cached: self.cached,
+        }
+    }
+
+    /// Whether the constraint failed its structural or verification check
+    fn is_failure(&self) -> bool {
+        self.status.is_failure()
+    }
+
+    /// Whether the current content has fewer approvals than its type requires
+    fn lacks_approvals(&self) -> bool {
+        self.approvals < self.required_approvals as usize
+    }
+
+    /// Whether this result should fail the run
+    fn is_blocking_failure(&self) -> bool {
+        self.lifecycle.is_enforced() && (self.is_failure() || self.lacks_approvals())
+    }
+}
+
+/// Outcome of checking one constraint, before approvals are considered
+#[derive(Debug)]
+struct CheckOutcome {
+    status: RunStatus,
+    output: Option<String>,
+    error: Option<String>,
+    duration_ms: u128,
+    cached: bool,
+}
+
+/// Counts of a run's results by outcome
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct Summary {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    timed_out: usize,
+    skipped: usize,
+    valid: usize,
+    invalid: usize,
+    /// Results reused from the verification cache
+    cached: usize,
+    /// Enforced constraints with fewer approvals than their type requires
+    missing_approvals: usize,
+    /// Draft and proposed constraints, which cannot fail the run
+    not_enforced: usize,
+}
+
+impl Summary {
+    /// Count the results of a run
+    fn new(results: &[ValidationResult]) -> Self {
+        let mut summary = Self {
+            total: results.len(),
+            ..Default::default()
+        };
+
+        for result in results {
+            match result.status {
+                RunStatus::Passed => summary.passed += 1,
+                RunStatus::Failed => summary.failed += 1,
+                RunStatus::TimedOut => summary.timed_out += 1,
+                RunStatus::Skipped => summary.skipped += 1,
+                RunStatus::Valid => summary.valid += 1,
+                RunStatus::Invalid => summary.invalid += 1,
+            }
+            if result.cached {
+                summary.cached += 1;
+            }
+            if !result.lifecycle.is_enforced() {
+                summary.not_enforced += 1;
+            } else if result.lacks_approvals() {
+                summary.missing_approvals += 1;
+            }
+        }
+
+        summary
+    }
+}
+
+/// How validate reports its results
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum ValidationStatus {
-    Passed,
-    Failed,
-    Skipped,
-    Valid,
-    Invalid,
+enum OutputFormat {
+    Human,
+    Json,
+    Junit,
+}
+
+impl OutputFormat {
+    /// Parse a `--format` value
+    fn parse(name: &str) -> Result<Self, ConstraintError> {
+        match name {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            "junit" => Ok(OutputFormat::Junit),
+            other => Err(ConstraintError::Validation(format!(
+                "Unknown output format '{}' (expected human, json or junit)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Version of the `--format json` schema, raised on any incompatible change
+///
+/// The schema is documented in docs/quickstart.md; adding fields is not an
+/// incompatible change.
+const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Top level of the `--format json` output
+#[derive(Debug, Serialize)]
+struct JsonReport<'a> {
+    schema_version: u32,
+    /// Recorded run, absent when there was nothing to validate
+    run_id: Option<&'a str>,
+    started_at: Option<DateTime<Utc>>,
+    git_revision: Option<&'a str>,
+    execute: bool,
+    /// Whether the run succeeded (the exit code is 0)
+    success: bool,
+    summary: Summary,
+    results: Vec<JsonResult<'a>>,
+}
+
+/// One constraint in the `--format json` output
+#[derive(Debug, Serialize)]
+struct JsonResult<'a> {
+    id: &'a str,
+    r#type: ConstraintType,
+    category: &'a str,
+    text: &'a str,
+    lifecycle: Lifecycle,
+    status: RunStatus,
+    output: Option<&'a str>,
+    error: Option<&'a str>,
+    duration_ms: u64,
+    cached: bool,
+    approvals: usize,
+    required_approvals: u32,
+    /// Whether this result fails the run
+    blocking: bool,
+}
+
+impl<'a> JsonReport<'a> {
+    /// Describe a run's results, which were recorded as `run` if there were any
+    fn new(run: Option<&'a ValidationRun>, results: &'a [ValidationResult], execute: bool) -> Self {
+        Self {
+            schema_version: JSON_SCHEMA_VERSION,
+            run_id: run.map(|run| run.id.as_str()),
+            started_at: run.map(|run| run.started_at),
+            git_revision: run.and_then(|run| run.git_revision.as_deref()),
+            execute,
+            success: !results.iter().any(|r| r.is_blocking_failure()),
+            summary: Summary::new(results),
+            results: results
+                .iter()
+                .map(|result| JsonResult {
+                    id: &result.constraint_id,
+                    r#type: result.constraint_type,
+                    category: &result.category,
+                    text: &result.constraint_text,
+                    lifecycle: result.lifecycle,
+                    status: result.status,
+                    output: result.output.as_deref(),
+                    error: result.error.as_deref(),
+                    duration_ms: result.duration_ms as u64,
+                    cached: result.cached,
+                    approvals: result.approvals,
+                    required_approvals: result.required_approvals,
+                    blocking: result.is_blocking_failure(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Settings shared by every verification in a run
+struct RunSettings<'a> {
+    /// Directory verification commands run relative to
+    project_dir: &'a Path,
+    /// Seconds a verification may run when its spec sets no limit
+    default_timeout: Option<u64>,
+    /// Verifications run at the same time
+    jobs: NonZeroUsize,
+    /// Where results of verifications with declared inputs are cached
+    cache: Option<&'a VerificationCache>,
+    /// Whether cached results may be reused (they are refreshed either way)
+    use_cached: bool,
+}
+
+/// Outcome of running one verification command
+#[derive(Debug)]
+struct VerificationRun {
+    passed: bool,
+    output: String,
+    /// Why a command that ran did not pass, beyond its exit code
+    failure: Option<String>,
+    /// Time limit the command was killed at, if it ran that long
+    timed_out_after: Option<u64>,
 }
 
 /// Run the validate command
 pub fn run(args: ValidateArgs) -> Result<(), Box<dyn std::error::Error>> {
     // Discover workspace
     let workspace = Workspace::discover()?;
-    let storage = JsonlStorage::new(workspace.constraints_dir());
+    let store = storage::open(&workspace)?;
+
+    let format = OutputFormat::parse(&workspace.config().output_format(args.format.as_deref()))?;
+    let audit = |constraint_id: Option<&str>, details| {
+        AuditLog::new(workspace.audit_path())
+            .append(
+                &workspace.config().actor(None),
+                "validated",
+                constraint_id,
+                details,
+            )
+            .map(|_| ())
+    };
 
     // Get constraints to validate
-    let constraints = get_constraints_to_validate(&storage, &args)?;
+    let constraints = get_constraints_to_validate(store.as_ref(), &args)?;
 
     if constraints.is_empty() {
-        println!("No constraints found to validate.");
+        // Recorded before any output, like runs that check something
+        audit(
+            None,
+            serde_json::json!({ "execute": args.execute, "passed": true, "results": {} }),
+        )?;
+        match format {
+            OutputFormat::Human => println!("No constraints found to validate."),
+            OutputFormat::Json => println!(
+                "{}",
+                serde_json::to_string_pretty(&JsonReport::new(None, &[], args.execute))?
+            ),
+            OutputFormat::Junit => print!("{}", junit_report(&[])),
+        }
+        if let Some(path) = &args.junit {
+            std::fs::write(path, junit_report(&[]))?;
+        }
         return Ok(());
     }
 
-    println!("Validating {} constraint(s)...", constraints.len());
-    println!();
+    if format == OutputFormat::Human {
+        println!("Validating {} constraint(s)...", constraints.len());
+        println!();
+    }
 
     // Run validation for each constraint
-    let mut results = Vec::new();
-    for constraint in &constraints {
-        let result = validate_constraint(constraint, args.execute)?;
-        results.push(result);
-    }
+    let project_dir = workspace.project_dir();
+    let mut run = ValidationRun::new(git_revision(&project_dir), args.execute);
+    let cache = VerificationCache::new(workspace.cache_dir());
+    let settings = RunSettings {
+        project_dir: &project_dir,
+        default_timeout: args
+            .timeout
+            .or(workspace.config().verification.timeout_secs),
+        jobs: args
+            .jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)),
+        cache: Some(&cache),
+        use_cached: !args.no_cache,
+    };
+    let results = validate_constraints(
+        store.as_ref(),
+        &constraints,
+        args.execute,
+        &workspace.config().approvals,
+        &settings,
+    )?;
+
+    // Keep the run for `constraint report` and each constraint's latest status
+    let has_failures = results.iter().any(|r| r.is_blocking_failure());
+    run.passed = !has_failures;
+    run.results = results
+        .iter()
+        .map(ValidationResult::to_run_result)
+        .collect();
+    // The audit entry goes first; a run it could not be logged for is not kept
+    let audited_id = args.id.as_ref().map(|_| constraints[0].id.as_str());
+    RunLog::new(workspace.runs_dir())
+        .save(&mut run, |run| audit(audited_id, audit_details(run)))?;
+    record_statuses(store.as_ref(), &constraints, &results)?;
 
     // Display results
-    display_results(&results, args.execute, args.verbose)?;
+    match format {
+        OutputFormat::Human => {
+            display_results(&results, args.execute, args.verbose)?;
+            println!();
+            println!("Recorded as run {} (see `constraint report`).", run.id);
+        }
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonReport::new(Some(&run), &results, args.execute))?
+        ),
+        OutputFormat::Junit => print!("{}", junit_report(&results)),
+    }
+    if let Some(path) = &args.junit {
+        std::fs::write(path, junit_report(&results))
+            .map_err(|e| format!("Failed to write JUnit report to {}: {}", path.display(), e))?;
+    }
 
-    // Exit with appropriate code
-    let has_failures = results.iter().any(|r| {
-        matches!(
-            r.status,
-            ValidationStatus::Failed | ValidationStatus::Invalid
-        )
-    });
+    // Exit with appropriate code; only accepted constraints can fail the run
     if has_failures {
         std::process::exit(1);
     }
@@ -71,99 +382,294 @@ pub fn run(args: ValidateArgs) -> Result<(), Box<dyn std::error::Error>> {
 
 /// Get constraints to validate based on arguments
 fn get_constraints_to_validate(
-    storage: &JsonlStorage,
+    store: &dyn ConstraintStore,
     args: &ValidateArgs,
 ) -> Result<Vec<Constraint>, ConstraintError> {
-    match (&args.category, &args.id) {
+    let mut constraints = match (&args.category, &args.id) {
         (Some(category), None) => {
             // Validate all constraints in a specific category
-            storage.read_category_constraints(category)
+            store.read_category_constraints(category)?
         }
         (None, Some(id)) => {
-            // Validate a specific constraint
-            storage.read_constraint_by_id(id).map(|c| vec![c])
+            // Validate a specific constraint, even when archived
+            return resolve_constraint(store, id).map(|c| vec![c]);
         }
         (None, None) => {
             // Validate all constraints
-            storage.read_all_constraints()
+            store.read_all_constraints()?
         }
         (Some(_), Some(_)) => {
             // Both category and ID specified - this is invalid
-            Err(ConstraintError::Validation(
+            return Err(ConstraintError::Validation(
                 "Cannot specify both --category and --id".to_string(),
-            ))
+            ));
         }
+    };
+
+    // Archived constraints are not enforced unless asked for
+    if !args.include_archived {
+        constraints.retain(|c| !c.is_archived());
     }
+
+    // Deprecated and superseded constraints are no longer in force
+    constraints.retain(|c| !c.lifecycle.is_retired());
+
+    Ok(constraints)
 }
 
-/// Validate a single constraint
+/// Validate a single constraint, including its approvals against `required_approvals`
 fn validate_constraint(
+    store: &dyn ConstraintStore,
     constraint: &Constraint,
     execute_verification: bool,
+    required_approvals: u32,
+    settings: &RunSettings,
 ) -> Result<ValidationResult, ConstraintError> {
+    let outcome = if execute_verification {
+        verify_constraint(constraint, settings)
+    } else {
+        check_structure(store, constraint)
+    };
+
+    Ok(ValidationResult::new(
+        constraint,
+        outcome,
+        required_approvals,
+    ))
+}
+
+/// Validate constraints, running verifications on up to `settings.jobs` threads
+///
+/// Results come back in the order of `constraints` however the commands
+/// interleave. Specs marked `serial` run after the others, one at a time.
+fn validate_constraints(
+    store: &dyn ConstraintStore,
+    constraints: &[Constraint],
+    execute_verification: bool,
+    approvals: &ApprovalConfig,
+    settings: &RunSettings,
+) -> Result<Vec<ValidationResult>, ConstraintError> {
+    if !execute_verification {
+        return constraints
+            .iter()
+            .map(|c| validate_constraint(store, c, false, approvals.required(c.r#type), settings))
+            .collect();
+    }
+
+    let (serial, parallel): (Vec<usize>, Vec<usize>) = (0..constraints.len()).partition(|&i| {
+        constraints[i]
+            .verification
+            .as_ref()
+            .is_some_and(|spec| spec.serial)
+    });
+
+    // Workers pull the next index until the parallel batch is exhausted
+    let next = AtomicUsize::new(0);
+    let finished = Mutex::new(Vec::with_capacity(constraints.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..settings.jobs.get().min(parallel.len()) {
+            scope.spawn(|| {
+                while let Some(&index) = parallel.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let outcome = verify_constraint(&constraints[index], settings);
+                    finished
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .push((index, outcome));
+                }
+            });
+        }
+    });
+
+    let mut outcomes: Vec<(usize, CheckOutcome)> = finished
+        .into_inner()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    for index in serial {
+        outcomes.push((index, verify_constraint(&constraints[index], settings)));
+    }
+    outcomes.sort_by_key(|(index, _)| *index);
+
+    Ok(outcomes
+        .into_iter()
+        .map(|(index, outcome)| {
+            let constraint = &constraints[index];
+            ValidationResult::new(constraint, outcome, approvals.required(constraint.r#type))
+        })
+        .collect())
+}
+
+/// Run a constraint's verification, if it has one
+///
+/// A verification with declared inputs reuses the cached result when its
+/// fingerprint is unchanged (unless caching is off), and records fresh
+/// results that ran to completion.
+fn verify_constraint(constraint: &Constraint, settings: &RunSettings) -> CheckOutcome {
     let start_time = std::time::Instant::now();
 
-    let (status, output, error) = if execute_verification {
-        if let Some(verification) = &constraint.verification {
+    let Some(verification) = &constraint.verification else {
+        // No verification command specified
+        return CheckOutcome {
+            status: RunStatus::Skipped,
+            output: None,
+            error: Some("No verification method specified".to_string()),
+            duration_ms: start_time.elapsed().as_millis(),
+            cached: false,
+        };
+    };
+
+    let cache =
+        settings.cache.and_then(
+            |cache| match fingerprint(verification, settings.project_dir) {
+                Ok(fingerprint) => fingerprint.map(|fingerprint| (cache, fingerprint)),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: not caching {}: cannot fingerprint its inputs: {}",
+                        constraint.id, e
+                    );
+                    None
+                }
+            },
+        );
+
+    let hit = cache
+        .as_ref()
+        .filter(|_| settings.use_cached)
+        .and_then(|(cache, fingerprint)| cache.get(&constraint.id, fingerprint));
+
+    let (run, cached) = match hit {
+        Some(hit) => (
+            Ok(VerificationRun {
+                passed: hit.passed,
+                output: hit.output,
+                failure: hit.failure,
+                timed_out_after: None,
+            }),
+            true,
+        ),
+        None => {
             // Execute verification command
-            match execute_verification_command(verification) {
-                Ok((success, cmd_output)) => {
-                    if success {
-                        (ValidationStatus::Passed, Some(cmd_output), None)
-                    } else {
-                        (ValidationStatus::Failed, Some(cmd_output), None)
+            let run = run_verification(verification, settings);
+            if let (Ok(run), Some((cache, fingerprint))) = (&run, &cache) {
+                if run.timed_out_after.is_none() {
+                    let entry = CachedVerification::new(
+                        fingerprint.clone(),
+                        run.passed,
+                        run.output.clone(),
+                        run.failure.clone(),
+                    );
+                    if let Err(e) = cache.put(&constraint.id, &entry) {
+                        eprintln!("Warning: could not cache {}: {}", constraint.id, e);
                     }
                 }
-                Err(e) => (ValidationStatus::Failed, None, Some(e.to_string())),
             }
-        } else {
-            // No verification command specified
-            (
-                ValidationStatus::Skipped,
-                None,
-                Some("No verification method specified".to_string()),
-            )
+            (run, false)
         }
-    } else {
-        // Perform structural validation
-        match constraint.validate() {
-            Ok(()) => (
-                ValidationStatus::Valid,
-                Some("Structural validation passed".to_string()),
-                None,
-            ),
-            Err(e) => (
-                ValidationStatus::Invalid,
-                None,
-                Some(format!("Structural validation failed: {}", e)),
-            ),
+    };
+
+    let (status, output, error) = match run {
+        Ok(run) => {
+            if let Some(timeout) = run.timed_out_after {
+                (
+                    RunStatus::TimedOut,
+                    Some(run.output),
+                    Some(format!("Timed out after {}s", timeout)),
+                )
+            } else if run.passed {
+                (RunStatus::Passed, Some(run.output), None)
+            } else {
+                (RunStatus::Failed, Some(run.output), run.failure)
+            }
         }
+        Err(e) => (RunStatus::Failed, None, Some(e.to_string())),
     };
 
-    let duration = start_time.elapsed().as_millis();
+    CheckOutcome {
+        status,
+        output,
+        error,
+        duration_ms: start_time.elapsed().as_millis(),
+        cached,
+    }
+}
 
-    Ok(ValidationResult {
-        constraint_id: constraint.id.clone(),
-        constraint_text: constraint.text.clone(),
+/// Check a constraint's structure, including relationship integrity
+fn check_structure(store: &dyn ConstraintStore, constraint: &Constraint) -> CheckOutcome {
+    let start_time = std::time::Instant::now();
+
+    let (status, output, error) = match constraint
+        .validate()
+        .and_then(|()| check_relations(store, constraint))
+    {
+        Ok(()) => (
+            RunStatus::Valid,
+            Some("Structural validation passed".to_string()),
+            None,
+        ),
+        Err(e) => (
+            RunStatus::Invalid,
+            None,
+            Some(format!("Structural validation failed: {}", e)),
+        ),
+    };
+
+    CheckOutcome {
         status,
         output,
         error,
-        duration_ms: duration,
-    })
+        duration_ms: start_time.elapsed().as_millis(),
+        cached: false,
+    }
 }
 
-/// Execute a verification command
-fn execute_verification_command(
-    command: &str,
-) -> Result<(bool, String), Box<dyn std::error::Error>> {
-    // For security, we'll use shell execution but with limited capabilities
-    // In a real implementation, you might want more sophisticated command parsing
+/// Run a verification spec
+///
+/// The command passes when its exit code is accepted and its stdout and
+/// stderr match the spec's patterns. A command still running at its time
+/// limit is killed along with everything it started.
+fn run_verification(
+    spec: &VerificationSpec,
+    settings: &RunSettings,
+) -> Result<VerificationRun, Box<dyn std::error::Error>> {
+    let dir = spec.working_dir(settings.project_dir);
+    let timeout = spec.timeout_secs.or(settings.default_timeout);
 
-    let output = Command::new("sh").arg("-c").arg(command).output()?;
+    let mut command = match spec.argv.split_first() {
+        Some((program, args)) => {
+            let mut command = Command::new(resolve_program(program, &dir));
+            command.args(args);
+            command
+        }
+        None => {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(spec.shell.as_deref().unwrap_or_default());
+            command
+        }
+    };
+    command.current_dir(&dir).envs(&spec.env);
+
+    let output = run_with_timeout(&mut command, timeout.map(Duration::from_secs))
+        .map_err(|e| format!("Failed to run `{}` in {}: {}", spec, dir.display(), e))?;
+    let timed_out_after = timeout.filter(|_| output.timed_out());
+    let (stdout, stderr) = (output.stdout, output.stderr);
 
-    let success = output.status.success();
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let exit_accepted = output
+        .status
+        .and_then(|status| status.code())
+        .is_some_and(|code| spec.accepts_exit_code(code));
+
+    let mut failure = None;
+    for (stream, pattern, text) in [
+        ("stdout", &spec.stdout_regex, &stdout),
+        ("stderr", &spec.stderr_regex, &stderr),
+    ] {
+        if let Some(pattern) = pattern {
+            if !Regex::new(pattern)?.is_match(text) {
+                failure = Some(format!("{} does not match /{}/", stream, pattern));
+                break;
+            }
+        }
+    }
 
     let combined_output = if stdout.is_empty() {
         stderr
@@ -173,7 +679,134 @@ fn execute_verification_command(
         format!("{}\n{}", stdout.trim(), stderr.trim())
     };
 
-    Ok((success, combined_output))
+    Ok(VerificationRun {
+        passed: exit_accepted && failure.is_none(),
+        output: combined_output,
+        failure,
+        timed_out_after,
+    })
+}
+
+/// Resolve a relative program path such as `./scripts/check.sh` against the
+/// working directory; bare names are left for a `PATH` lookup
+fn resolve_program(program: &str, dir: &Path) -> PathBuf {
+    let path = Path::new(program);
+    if path.is_relative() && path.components().count() > 1 {
+        dir.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Store the outcome of each check in its constraint's record, where it changed
+///
+/// `results` must be in the order of `constraints`, as `validate_constraints`
+/// returns them.
+fn record_statuses(
+    store: &dyn ConstraintStore,
+    constraints: &[Constraint],
+    results: &[ValidationResult],
+) -> Result<(), ConstraintError> {
+    for (constraint, result) in constraints.iter().zip(results) {
+        if let Some(status) = result.status.recorded_status() {
+            if status != constraint.validation_status {
+                store.record_validation_status(constraint, status)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Commit checked out in the project directory, if it is a git repository
+fn git_revision(project_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(project_dir)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Summarize a validation run for the audit log
+fn audit_details(run: &ValidationRun) -> serde_json::Value {
+    let statuses: serde_json::Map<String, serde_json::Value> = run
+        .results
+        .iter()
+        .map(|r| (r.constraint_id.clone(), r.status.label().into()))
+        .collect();
+
+    serde_json::json!({
+        "run": run.id,
+        "execute": run.execute,
+        "passed": run.passed,
+        "results": statuses,
+    })
+}
+
+/// Build a JUnit XML report with one testsuite per category
+///
+/// Each constraint is a testcase. Results that fail the run are failures
+/// carrying the captured output; skipped verifications and failures of
+/// constraints that are not enforced are reported as skipped.
+fn junit_report(results: &[ValidationResult]) -> String {
+    let mut suites: BTreeMap<&str, Vec<TestCase>> = BTreeMap::new();
+    for result in results {
+        let outcome = if result.is_blocking_failure() {
+            let message = if result.is_failure() {
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "Verification failed".to_string())
+            } else {
+                format!(
+                    "Approvals: {} of {} required",
+                    result.approvals, result.required_approvals
+                )
+            };
+            TestOutcome::Failed {
+                message,
+                kind: result.status.label().to_string(),
+            }
+        } else if result.is_failure() {
+            TestOutcome::Skipped {
+                message: format!(
+                    "{} ({}, not enforced)",
+                    result.status.label(),
+                    result.lifecycle
+                ),
+            }
+        } else if result.status == RunStatus::Skipped {
+            TestOutcome::Skipped {
+                message: result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "Skipped".to_string()),
+            }
+        } else {
+            TestOutcome::Passed
+        };
+
+        suites.entry(&result.category).or_default().push(TestCase {
+            name: format!("{}: {}", result.constraint_id, result.constraint_text),
+            classname: result.category.clone(),
+            duration_ms: result.duration_ms as u64,
+            outcome,
+            output: result.output.clone(),
+        });
+    }
+
+    let suites: Vec<TestSuite> = suites
+        .into_iter()
+        .map(|(name, cases)| TestSuite {
+            name: name.to_string(),
+            cases,
+        })
+        .collect();
+    junit::render("constraint validate", &suites)
 }
 
 /// Display validation results
@@ -182,41 +815,35 @@ fn display_results(
     executed: bool,
     verbose: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut passed = 0;
-    let mut failed = 0;
-    let mut skipped = 0;
-    let mut valid = 0;
-    let mut invalid = 0;
+    let summary = Summary::new(results);
 
     for result in results {
-        match result.status {
-            ValidationStatus::Passed => passed += 1,
-            ValidationStatus::Failed => failed += 1,
-            ValidationStatus::Skipped => skipped += 1,
-            ValidationStatus::Valid => valid += 1,
-            ValidationStatus::Invalid => invalid += 1,
-        }
-
         // Display individual result
-        let status_icon = match result.status {
-            ValidationStatus::Passed => "✅",
-            ValidationStatus::Failed => "❌",
-            ValidationStatus::Skipped => "⏭️",
-            ValidationStatus::Valid => "✅",
-            ValidationStatus::Invalid => "❌",
-        };
+        let status_icon = result.status.icon();
 
-        let status_text = match result.status {
-            ValidationStatus::Passed => "PASSED",
-            ValidationStatus::Failed => "FAILED",
-            ValidationStatus::Skipped => "SKIPPED",
-            ValidationStatus::Valid => "VALID",
-            ValidationStatus::Invalid => "INVALID",
+        let status_text = if result.cached {
+            format!("{} (cached)", result.status.label())
+        } else {
+            result.status.label().to_string()
         };
 
-        println!("{} {} - {}", status_icon, result.constraint_id, status_text);
+        if result.lifecycle.is_enforced() {
+            println!("{} {} - {}", status_icon, result.constraint_id, status_text);
+        } else {
+            println!(
+                "{} {} - {} ({}, not enforced)",
+                status_icon, result.constraint_id, status_text, result.lifecycle
+            );
+        }
         println!("   {}", result.constraint_text);
 
+        if result.lacks_approvals() {
+            println!(
+                "   ⚠️ Approvals: {} of {} required",
+                result.approvals, result.required_approvals
+            );
+        }
+
         if let Some(output) = &result.output {
             if verbose || executed {
                 println!("   Output: {}", output);
@@ -244,25 +871,52 @@ fn display_results(
     // Display summary
     if executed {
         println!("Verification Summary:");
-        println!("  ✅ Passed: {}", passed);
-        println!("  ❌ Failed: {}", failed);
-        println!("  ⏭️ Skipped: {}", skipped);
+        println!("  ✅ Passed: {}", summary.passed);
+        println!("  ❌ Failed: {}", summary.failed);
+        if summary.timed_out > 0 {
+            println!("  ⏱️ Timed out: {}", summary.timed_out);
+        }
+        println!("  ⏭️ Skipped: {}", summary.skipped);
+        if summary.cached > 0 {
+            println!("  💾 From cache: {}", summary.cached);
+        }
     } else {
         println!("Structural Validation Summary:");
-        println!("  ✅ Valid: {}", valid);
-        println!("  ❌ Invalid: {}", invalid);
+        println!("  ✅ Valid: {}", summary.valid);
+        println!("  ❌ Invalid: {}", summary.invalid);
     }
-    println!("  📊 Total: {}", results.len());
+    let unapproved = summary.missing_approvals;
+    if unapproved > 0 {
+        println!("  ⚠️ Missing approvals: {}", unapproved);
+    }
+    if summary.not_enforced > 0 {
+        println!(
+            "  📝 Not enforced (draft/proposed): {}",
+            summary.not_enforced
+        );
+    }
+    println!("  📊 Total: {}", summary.total);
 
-    if executed && failed > 0 {
+    let failing = results
+        .iter()
+        .filter(|r| r.lifecycle.is_enforced() && r.is_failure())
+        .count();
+    if failing > 0 || unapproved > 0 {
         println!();
-        println!("❌ Some verifications failed. Check the output above for details.");
+        if failing > 0 && executed {
+            println!("❌ Some verifications failed. Check the output above for details.");
+        } else if failing > 0 {
+            println!("❌ Some constraints are invalid. Check the output above for details.");
+        }
+        if unapproved > 0 {
+            println!(
+                "❌ {} constraint(s) lack the approvals their type requires (see `constraint approve`).",
+                unapproved
+            );
+        }
     } else if executed {
         println!();
         println!("✅ All verifications completed successfully!");
-    } else if invalid > 0 {
-        println!();
-        println!("❌ Some constraints are invalid. Check the output above for details.");
     } else {
         println!();
         println!("✅ All constraints are structurally valid!");
@@ -275,13 +929,24 @@ fn display_results(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::constraint::{Constraint, ConstraintParams, ConstraintType};
+    use crate::core::constraint::{Constraint, ConstraintParams, ConstraintType, ValidationStatus};
+    use crate::storage::MemoryStore;
+
+    fn settings(project_dir: &Path) -> RunSettings<'_> {
+        RunSettings {
+            project_dir,
+            default_timeout: None,
+            jobs: NonZeroUsize::MIN,
+            cache: None,
+            use_cached: true,
+        }
+    }
 
     #[test]
     fn test_validation_status_display() {
         // Test that validation status works correctly
-        let status = ValidationStatus::Passed;
-        assert!(matches!(status, ValidationStatus::Passed));
+        let status = RunStatus::Passed;
+        assert!(matches!(status, RunStatus::Passed));
     }
 
     #[test]
@@ -297,12 +962,23 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
         // Test structural validation (execute = false)
-        let result = validate_constraint(&constraint, false).unwrap();
-        assert!(matches!(result.status, ValidationStatus::Valid));
+        let result = validate_constraint(
+            &MemoryStore::new(),
+            &constraint,
+            false,
+            0,
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        assert!(matches!(result.status, RunStatus::Valid));
         assert!(result.output.is_some());
         assert!(result.error.is_none());
     }
@@ -320,6 +996,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -327,8 +1007,15 @@ mod tests {
         constraint.category = "Invalid-Category".to_string();
 
         // Test structural validation (execute = false)
-        let result = validate_constraint(&constraint, false).unwrap();
-        assert!(matches!(result.status, ValidationStatus::Invalid));
+        let result = validate_constraint(
+            &MemoryStore::new(),
+            &constraint,
+            false,
+            0,
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        assert!(matches!(result.status, RunStatus::Invalid));
         assert!(result.output.is_none());
         assert!(result.error.is_some());
         assert!(result
@@ -349,13 +1036,24 @@ mod tests {
             tags: vec![],
             priority: None,
             references: "".to_string(),
-            verification: Some("echo 'test passed'".to_string()),
+            verification: Some(VerificationSpec::shell("echo 'test passed'")),
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
         // Test verification execution (execute = true)
-        let result = validate_constraint(&constraint, true).unwrap();
-        assert!(matches!(result.status, ValidationStatus::Passed));
+        let result = validate_constraint(
+            &MemoryStore::new(),
+            &constraint,
+            true,
+            0,
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        assert!(matches!(result.status, RunStatus::Passed));
         assert!(result.output.is_some());
         assert!(result.error.is_none());
         assert!(result.output.unwrap().contains("test passed"));
@@ -374,12 +1072,23 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
         // Test verification execution (execute = true)
-        let result = validate_constraint(&constraint, true).unwrap();
-        assert!(matches!(result.status, ValidationStatus::Skipped));
+        let result = validate_constraint(
+            &MemoryStore::new(),
+            &constraint,
+            true,
+            0,
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        assert!(matches!(result.status, RunStatus::Skipped));
         assert!(result.output.is_none());
         assert!(result.error.is_some());
         assert!(result
@@ -400,14 +1109,537 @@ mod tests {
             tags: vec![],
             priority: None,
             references: "".to_string(),
-            verification: Some("exit 1".to_string()),
+            verification: Some(VerificationSpec::shell("exit 1")),
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
         // Test verification execution (execute = true)
-        let result = validate_constraint(&constraint, true).unwrap();
-        assert!(matches!(result.status, ValidationStatus::Failed));
+        let result = validate_constraint(
+            &MemoryStore::new(),
+            &constraint,
+            true,
+            0,
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        assert!(matches!(result.status, RunStatus::Failed));
         assert!(result.output.is_some());
         assert!(result.error.is_none());
     }
+
+    #[test]
+    fn test_verification_spec_execution() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("api")).unwrap();
+        std::fs::write(temp_dir.path().join("api/VERSION"), "1.2.0\n").unwrap();
+
+        let run = |spec: VerificationSpec| {
+            let constraint = Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: "release".to_string(),
+                text: "Version file is present".to_string(),
+                author: "test-author".to_string(),
+                verification: Some(spec),
+                ..Default::default()
+            })
+            .unwrap();
+            validate_constraint(
+                &MemoryStore::new(),
+                &constraint,
+                true,
+                0,
+                &settings(temp_dir.path()),
+            )
+            .unwrap()
+        };
+
+        // argv runs without a shell, from cwd under the project directory
+        let spec = VerificationSpec {
+            argv: vec!["cat".to_string(), "VERSION".to_string()],
+            cwd: Some("api".to_string()),
+            stdout_regex: Some(r"^1\.\d+\.\d+".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(run(spec.clone()).status, RunStatus::Passed);
+
+        // Output assertions can fail a command that exits 0
+        let result = run(VerificationSpec {
+            stdout_regex: Some("^2\\.".to_string()),
+            ..spec
+        });
+        assert_eq!(result.status, RunStatus::Failed);
+        assert!(result.error.unwrap().contains("stdout does not match"));
+
+        // Environment and accepted exit codes
+        let result = run(VerificationSpec {
+            env: [("LEVEL".to_string(), "3".to_string())].into(),
+            exit_codes: vec![3],
+            stderr_regex: Some("level 3".to_string()),
+            ..VerificationSpec::shell("echo \"level $LEVEL\" >&2; exit $LEVEL")
+        });
+        assert_eq!(result.status, RunStatus::Passed);
+        assert_eq!(result.output.as_deref(), Some("level 3\n"));
+
+        // A missing working directory is reported, not a panic
+        let result = run(VerificationSpec {
+            cwd: Some("missing".to_string()),
+            ..VerificationSpec::shell("true")
+        });
+        assert_eq!(result.status, RunStatus::Failed);
+        assert!(result.error.unwrap().contains("missing"));
+    }
+
+    #[test]
+    fn test_verification_timeout() {
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "testing".to_string(),
+            text: "Test suite finishes".to_string(),
+            author: "test-author".to_string(),
+            verification: Some(VerificationSpec::shell("echo running; sleep 30")),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // The run-wide default applies when the spec sets no limit
+        let settings = RunSettings {
+            default_timeout: Some(1),
+            ..settings(Path::new("."))
+        };
+        let result =
+            validate_constraint(&MemoryStore::new(), &constraint, true, 0, &settings).unwrap();
+        assert_eq!(result.status, RunStatus::TimedOut);
+        assert_eq!(result.output.as_deref(), Some("running\n"));
+        assert_eq!(result.error.as_deref(), Some("Timed out after 1s"));
+        assert!(result.is_blocking_failure());
+
+        // A limit on the spec takes precedence
+        let mut quick = constraint.clone();
+        quick.verification = Some(VerificationSpec {
+            timeout_secs: Some(60),
+            ..VerificationSpec::shell("echo done")
+        });
+        let result = validate_constraint(&MemoryStore::new(), &quick, true, 0, &settings).unwrap();
+        assert_eq!(result.status, RunStatus::Passed);
+    }
+
+    #[test]
+    fn test_parallel_verification_keeps_order() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut constraints = vec![];
+        for i in 0..4 {
+            constraints.push(
+                Constraint::new(ConstraintParams {
+                    r#type: ConstraintType::Must,
+                    category: "testing".to_string(),
+                    text: format!("Slow check {}", i),
+                    author: "test-author".to_string(),
+                    // Each waits (up to 20s) until all four have started, so
+                    // they can only pass by overlapping; later ones then finish first
+                    verification: Some(VerificationSpec::shell(format!(
+                        "touch started-{i}; n=0; \
+                         while [ $(ls started-* | wc -l) -lt 4 ]; do \
+                         n=$((n+1)); [ $n -ge 400 ] && exit 1; sleep 0.05; done; \
+                         sleep 0.{}; echo {i}",
+                        8 - 2 * i,
+                    ))),
+                    ..Default::default()
+                })
+                .unwrap(),
+            );
+        }
+
+        // Serial checks share a lock file and would trip over each other
+        for i in 0..2 {
+            constraints.push(
+                Constraint::new(ConstraintParams {
+                    r#type: ConstraintType::Must,
+                    category: "testing".to_string(),
+                    text: format!("Stateful check {}", i),
+                    author: "test-author".to_string(),
+                    verification: Some(VerificationSpec {
+                        serial: true,
+                        ..VerificationSpec::shell(
+                            "test ! -e lock && touch lock && sleep 0.3 && rm lock",
+                        )
+                    }),
+                    ..Default::default()
+                })
+                .unwrap(),
+            );
+        }
+
+        let settings = RunSettings {
+            jobs: NonZeroUsize::new(4).unwrap(),
+            ..settings(temp_dir.path())
+        };
+        let results = validate_constraints(
+            &MemoryStore::new(),
+            &constraints,
+            true,
+            &ApprovalConfig::default(),
+            &settings,
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = results.iter().map(|r| r.constraint_id.as_str()).collect();
+        let expected: Vec<&str> = constraints.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, expected);
+        for (i, result) in results.iter().take(4).enumerate() {
+            assert_eq!(result.output.as_deref(), Some(format!("{}\n", i).as_str()));
+        }
+        assert!(results.iter().all(|r| r.status == RunStatus::Passed));
+    }
+
+    #[test]
+    fn test_cached_results_follow_inputs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path();
+        std::fs::write(project.join("input.txt"), "v1").unwrap();
+        let cache = VerificationCache::new(project.join(".newton/cache"));
+
+        // Each real run appends to runs.log, so cache hits leave it alone
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "testing".to_string(),
+            text: "Input is checked".to_string(),
+            author: "test-author".to_string(),
+            verification: Some(VerificationSpec {
+                inputs: vec!["*.txt".to_string()],
+                ..VerificationSpec::shell("echo run >> runs.log; cat input.txt")
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut settings = RunSettings {
+            cache: Some(&cache),
+            ..settings(project)
+        };
+        let runs = || {
+            std::fs::read_to_string(project.join("runs.log"))
+                .unwrap()
+                .lines()
+                .count()
+        };
+
+        assert!(!verify_constraint(&constraint, &settings).cached);
+        let second = verify_constraint(&constraint, &settings);
+        assert!(second.cached);
+        assert_eq!(second.status, RunStatus::Passed);
+        assert_eq!(second.output.as_deref(), Some("v1"));
+        assert_eq!(runs(), 1);
+
+        // A changed input invalidates the entry
+        std::fs::write(project.join("input.txt"), "v2").unwrap();
+        let third = verify_constraint(&constraint, &settings);
+        assert!(!third.cached);
+        assert_eq!(third.output.as_deref(), Some("v2"));
+        assert_eq!(runs(), 2);
+
+        // --no-cache runs again even though nothing changed
+        settings.use_cached = false;
+        assert!(!verify_constraint(&constraint, &settings).cached);
+        assert_eq!(runs(), 3);
+    }
+
+    #[test]
+    fn test_record_statuses() {
+        let store = MemoryStore::new();
+        let mut constraints = vec![];
+        for (text, verification) in [
+            ("Passing rule", Some("true")),
+            ("Failing rule", Some("exit 1")),
+            ("Unverified rule", None),
+        ] {
+            let constraint = Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: "testing".to_string(),
+                text: text.to_string(),
+                author: "test-author".to_string(),
+                verification: verification.map(VerificationSpec::shell),
+                ..Default::default()
+            })
+            .unwrap();
+            store.write_constraint(&constraint).unwrap();
+            constraints.push(constraint);
+        }
+
+        let results = validate_constraints(
+            &store,
+            &constraints,
+            true,
+            &ApprovalConfig::default(),
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        record_statuses(&store, &constraints, &results).unwrap();
+
+        // Skipped verifications leave the stored status alone
+        let stored: Vec<ValidationStatus> = constraints
+            .iter()
+            .map(|c| {
+                store
+                    .read_constraint_by_id(&c.id)
+                    .unwrap()
+                    .validation_status
+            })
+            .collect();
+        assert_eq!(
+            stored,
+            vec![
+                ValidationStatus::Valid,
+                ValidationStatus::Invalid,
+                ValidationStatus::Valid
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_report() {
+        let store = MemoryStore::new();
+        let constraints: Vec<Constraint> = [
+            ("Passing rule", "true", Lifecycle::Accepted),
+            ("Failing rule", "echo broken; exit 1", Lifecycle::Accepted),
+            ("Draft rule", "exit 1", Lifecycle::Draft),
+        ]
+        .into_iter()
+        .map(|(text, script, lifecycle)| {
+            Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: "testing".to_string(),
+                text: text.to_string(),
+                author: "test-author".to_string(),
+                verification: Some(VerificationSpec::shell(script)),
+                lifecycle,
+                ..Default::default()
+            })
+            .unwrap()
+        })
+        .collect();
+
+        let results = validate_constraints(
+            &store,
+            &constraints,
+            true,
+            &ApprovalConfig::default(),
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        let report = serde_json::to_value(JsonReport::new(None, &results, true)).unwrap();
+
+        assert_eq!(report["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(report["run_id"], serde_json::Value::Null);
+        assert_eq!(report["success"], false);
+        assert_eq!(report["summary"]["total"], 3);
+        assert_eq!(report["summary"]["passed"], 1);
+        assert_eq!(report["summary"]["failed"], 2);
+        assert_eq!(report["summary"]["not_enforced"], 1);
+
+        let failing = &report["results"][1];
+        assert_eq!(failing["id"], constraints[1].id.as_str());
+        assert_eq!(failing["type"], "MUST");
+        assert_eq!(failing["category"], "testing");
+        assert_eq!(failing["status"], "failed");
+        assert_eq!(failing["output"], "broken\n");
+        assert_eq!(failing["blocking"], true);
+        assert!(failing["duration_ms"].is_u64());
+
+        // Failing drafts are reported without failing the run
+        assert_eq!(report["results"][2]["lifecycle"], "draft");
+        assert_eq!(report["results"][2]["blocking"], false);
+
+        assert!(OutputFormat::parse("xml").is_err());
+    }
+
+    #[test]
+    fn test_junit_report() {
+        let store = MemoryStore::new();
+        let constraints: Vec<Constraint> = [
+            ("security", Some("true"), Lifecycle::Accepted),
+            ("security", Some("echo leaked; exit 1"), Lifecycle::Accepted),
+            ("testing", None, Lifecycle::Accepted),
+            ("testing", Some("exit 1"), Lifecycle::Draft),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (category, script, lifecycle))| {
+            Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: category.to_string(),
+                text: format!("Rule {}", i),
+                author: "test-author".to_string(),
+                verification: script.map(VerificationSpec::shell),
+                lifecycle,
+                ..Default::default()
+            })
+            .unwrap()
+        })
+        .collect();
+
+        let results = validate_constraints(
+            &store,
+            &constraints,
+            true,
+            &ApprovalConfig::default(),
+            &settings(Path::new(".")),
+        )
+        .unwrap();
+        let xml = junit_report(&results);
+
+        assert!(xml.contains(r#"tests="4" failures="1" errors="0" skipped="2""#));
+        assert!(xml.contains(r#"<testsuite name="security" tests="2" failures="1""#));
+        assert!(xml.contains(r#"<testsuite name="testing" tests="2" failures="0""#));
+        assert!(xml.contains(&format!(
+            r#"<testcase name="{}: Rule 1" classname="security""#,
+            constraints[1].id
+        )));
+        assert!(xml.contains(r#"type="FAILED">leaked"#));
+        assert!(xml.contains(r#"<skipped message="No verification method specified"/>"#));
+        assert!(xml.contains(r#"<skipped message="FAILED (draft, not enforced)"/>"#));
+    }
+
+    #[test]
+    fn test_archived_constraints_skipped_by_default() {
+        let store = crate::storage::MemoryStore::new();
+        let mut archived = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Retired requirement".to_string(),
+            author: "test-author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        archived.archive().unwrap();
+        store.write_constraint(&archived).unwrap();
+
+        let mut args = ValidateArgs {
+            category: None,
+            id: None,
+            execute: false,
+            verbose: false,
+            include_archived: false,
+            timeout: None,
+            jobs: None,
+            no_cache: false,
+            format: None,
+            junit: None,
+        };
+        assert!(get_constraints_to_validate(&store, &args)
+            .unwrap()
+            .is_empty());
+
+        args.include_archived = true;
+        assert_eq!(get_constraints_to_validate(&store, &args).unwrap().len(), 1);
+
+        // An explicit ID is validated even when archived
+        args.include_archived = false;
+        args.id = Some(archived.id.clone());
+        assert_eq!(get_constraints_to_validate(&store, &args).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_structural_validation_dangling_relation() {
+        let store = MemoryStore::new();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Use argon2id".to_string(),
+            author: "test-author".to_string(),
+            depends_on: vec!["nt-gone01".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+
+        let result =
+            validate_constraint(&store, &constraint, false, 0, &settings(Path::new("."))).unwrap();
+        assert!(matches!(result.status, RunStatus::Invalid));
+        assert!(result.error.unwrap().contains("nt-gone01"));
+    }
+
+    #[test]
+    fn test_only_accepted_constraints_are_enforced() {
+        let store = MemoryStore::new();
+        for (text, lifecycle) in [
+            ("Accepted rule", Lifecycle::Accepted),
+            ("Draft rule", Lifecycle::Draft),
+            ("Deprecated rule", Lifecycle::Deprecated),
+        ] {
+            let constraint = Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: "security".to_string(),
+                text: text.to_string(),
+                author: "test-author".to_string(),
+                verification: Some(VerificationSpec::shell("exit 1")),
+                lifecycle,
+                ..Default::default()
+            })
+            .unwrap();
+            store.write_constraint(&constraint).unwrap();
+        }
+
+        let args = ValidateArgs {
+            category: None,
+            id: None,
+            execute: true,
+            verbose: false,
+            include_archived: false,
+            timeout: None,
+            jobs: None,
+            no_cache: false,
+            format: None,
+            junit: None,
+        };
+        let constraints = get_constraints_to_validate(&store, &args).unwrap();
+        assert_eq!(constraints.len(), 2);
+        assert!(constraints.iter().all(|c| !c.lifecycle.is_retired()));
+
+        // Failing drafts are reported but do not block
+        for constraint in &constraints {
+            let result =
+                validate_constraint(&store, constraint, true, 0, &settings(Path::new(".")))
+                    .unwrap();
+            assert_eq!(result.status, RunStatus::Failed);
+            assert_eq!(
+                result.is_blocking_failure(),
+                constraint.lifecycle == Lifecycle::Accepted
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_approvals_block_enforced_constraints() {
+        let store = MemoryStore::new();
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "All passwords must be hashed".to_string(),
+            author: "test-author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let result =
+            validate_constraint(&store, &constraint, false, 1, &settings(Path::new("."))).unwrap();
+        assert_eq!(result.status, RunStatus::Valid);
+        assert!(result.lacks_approvals());
+        assert!(result.is_blocking_failure());
+
+        constraint.approve("reviewer", None).unwrap();
+        let result =
+            validate_constraint(&store, &constraint, false, 1, &settings(Path::new("."))).unwrap();
+        assert!(!result.lacks_approvals());
+        assert!(!result.is_blocking_failure());
+
+        // Drafts are flagged but do not block
+        constraint.lifecycle = Lifecycle::Draft;
+        let result =
+            validate_constraint(&store, &constraint, false, 2, &settings(Path::new("."))).unwrap();
+        assert!(result.lacks_approvals());
+        assert!(!result.is_blocking_failure());
+    }
 }
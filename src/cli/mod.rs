@@ -7,14 +7,35 @@ pub use args::*;
 #[allow(unused_imports)]
 pub use commands::*;
 
+use crate::core::lifecycle::Lifecycle;
+
 /// Run the CLI application
 pub fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     match cli.command {
+        Commands::Init(args) => commands::init::run(args),
         Commands::Add(args) => commands::add::run(args),
         Commands::List(args) => commands::list::run(args),
         Commands::Search(args) => commands::search::run(args),
         Commands::Patch(args) => commands::patch::run(args),
+        Commands::Move(args) => commands::r#move::run(args),
+        Commands::Aliases(args) => commands::aliases::run(args),
+        Commands::Reid(args) => commands::reid::run(args),
         Commands::Delete(args) => commands::delete::run(args),
+        Commands::Restore(args) => commands::restore::run(args),
+        Commands::Propose(args) => commands::lifecycle::run(args, Lifecycle::Proposed),
+        Commands::Accept(args) => commands::lifecycle::run(args, Lifecycle::Accepted),
+        Commands::Withdraw(args) => commands::lifecycle::run(args, Lifecycle::Draft),
+        Commands::Deprecate(args) => commands::lifecycle::run(args, Lifecycle::Deprecated),
+        Commands::Supersede(args) => commands::lifecycle::run_supersede(args),
+        Commands::Approve(args) => commands::approve::run(args),
+        Commands::Sign(args) => commands::sign::run(args),
+        Commands::VerifySignatures(args) => commands::sign::run_verify(args),
+        Commands::History(args) => commands::history::run(args),
+        Commands::Links(args) => commands::links::run(args),
+        Commands::Graph(args) => commands::graph::run(args),
         Commands::Validate(args) => commands::validate::run(args),
+        Commands::Report(args) => commands::report::run(args),
+        Commands::Storage(args) => commands::storage::run(args),
+        Commands::Audit(args) => commands::audit::run(args),
     }
 }
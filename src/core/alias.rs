@@ -0,0 +1,78 @@
+//! Alias records mapping retired constraint IDs to their replacements
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// A retired ID that now redirects to another constraint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alias {
+    /// The retired ID
+    pub alias: String,
+
+    /// The ID it redirects to
+    pub target: String,
+
+    /// When the alias was recorded
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Alias {
+    /// Record that `alias` now refers to `target`
+    pub fn new(alias: &str, target: &str) -> Self {
+        Self {
+            alias: alias.to_string(),
+            target: target.to_string(),
+            // Stored with second precision, so truncate up front
+            created_at: Utc::now().trunc_subsecs(0),
+        }
+    }
+}
+
+/// Follow a chain of aliases to the ID it finally redirects to
+///
+/// Returns `None` when `id` is not an alias. Cycles stop at the last ID
+/// before the chain repeats.
+pub fn resolve_chain(aliases: &[Alias], id: &str) -> Option<String> {
+    let targets: BTreeMap<&str, &str> = aliases
+        .iter()
+        .map(|a| (a.alias.as_str(), a.target.as_str()))
+        .collect();
+
+    let mut current = id;
+    let mut seen = HashSet::from([id]);
+    while let Some(&next) = targets.get(current) {
+        if !seen.insert(next) {
+            break;
+        }
+        current = next;
+    }
+
+    (current != id).then(|| current.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_chain() {
+        let aliases = vec![
+            Alias::new("nt-aaaaaa", "nt-bbbbbb"),
+            Alias::new("nt-bbbbbb", "nt-cccccc"),
+            Alias::new("nt-xxxxxx", "nt-yyyyyy"),
+            Alias::new("nt-yyyyyy", "nt-xxxxxx"),
+        ];
+
+        assert_eq!(
+            resolve_chain(&aliases, "nt-aaaaaa").as_deref(),
+            Some("nt-cccccc")
+        );
+        assert_eq!(resolve_chain(&aliases, "nt-cccccc"), None);
+        assert_eq!(
+            resolve_chain(&aliases, "nt-xxxxxx").as_deref(),
+            Some("nt-yyyyyy")
+        );
+    }
+}
@@ -0,0 +1,34 @@
+//! Review sign-offs recorded on constraints
+//!
+//! An approval is bound to the content hash of the constraint at the time it
+//! was given (see [`crate::core::constraint::Constraint::content_hash`]). Any
+//! later change to the content produces a different hash, so earlier approvals
+//! stop counting without having to be removed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A reviewer's sign-off on one version of a constraint's content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    /// Who signed off (never the constraint's author)
+    pub reviewer: String,
+
+    /// Content hash the approval applies to
+    pub content_hash: String,
+
+    /// When the approval was given
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub approved_at: DateTime<Utc>,
+}
+
+impl Approval {
+    /// Record an approval by `reviewer` for the given content hash
+    pub fn new(reviewer: &str, content_hash: &str) -> Self {
+        Self {
+            reviewer: reviewer.to_string(),
+            content_hash: content_hash.to_string(),
+            approved_at: Utc::now(),
+        }
+    }
+}
@@ -1,10 +1,20 @@
 //! Constraint data model and validation logic
 
+use std::collections::{BTreeMap, HashMap};
+
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
+use crate::core::approval::Approval;
 use crate::core::error::ConstraintError;
+use crate::core::lifecycle::Lifecycle;
+use crate::core::loader::CURRENT_VERSION;
+use crate::core::relation::{Relation, RelationKind};
+use crate::core::signature::RecordSignature;
+use crate::core::verification::VerificationSpec;
+use crate::utils::id::IdGenerator;
 
 /// RFC 2119 constraint types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -18,8 +28,21 @@ pub enum ConstraintType {
     Forbidden, // Explicitly prohibited
 }
 
+impl ConstraintType {
+    /// The uppercase RFC 2119 keyword, as stored on disk
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConstraintType::Must => "MUST",
+            ConstraintType::Shall => "SHALL",
+            ConstraintType::Should => "SHOULD",
+            ConstraintType::May => "MAY",
+            ConstraintType::Forbidden => "FORBIDDEN",
+        }
+    }
+}
+
 /// Current validation state of a constraint
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ValidationStatus {
     Valid,   // Constraint is properly formed and verifiable
@@ -33,7 +56,10 @@ pub struct Constraint {
     /// Integer version for format evolution (required, >= 1)
     pub version: u32,
 
-    /// Unique hash-based identifier (format: nt-xxxxxx, required)
+    /// Unique hash-based identifier (format: `<prefix>-xxxxxx`, default prefix nt, required)
+    ///
+    /// Derived from the content when the constraint is created and kept for its
+    /// whole life: later edits, moves and retypes never reissue it.
     pub id: String,
 
     /// RFC 2119 constraint type (required)
@@ -61,9 +87,21 @@ pub struct Constraint {
     #[serde(skip_serializing_if = "String::is_empty", default)]
     pub references: String,
 
-    /// Verification command/script/description (optional)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub verification: Option<String>,
+    /// How to verify the constraint holds (optional)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub verification: Option<VerificationSpec>,
+
+    /// IDs of constraints this one refines or builds on
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<String>,
+
+    /// IDs of constraints this one replaces
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub supersedes: Vec<String>,
+
+    /// IDs of constraints that cannot hold together with this one
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub conflicts_with: Vec<String>,
 
     /// Creation timestamp (required)
     #[serde(with = "chrono::serde::ts_seconds")]
@@ -73,8 +111,29 @@ pub struct Constraint {
     #[serde(with = "chrono::serde::ts_seconds")]
     pub updated_at: DateTime<Utc>,
 
-    /// Current validation state (required)
+    /// Outcome of the latest validation run (required)
     pub validation_status: ValidationStatus,
+
+    /// Lifecycle state; only accepted constraints are enforced (defaults to accepted)
+    #[serde(default)]
+    pub lifecycle: Lifecycle,
+
+    /// Review sign-offs; only those matching the current content hash count
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub approvals: Vec<Approval>,
+
+    /// When the constraint was archived; archived constraints stay on disk but
+    /// are hidden from list, search and validate by default
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "chrono::serde::ts_seconds_option"
+    )]
+    pub archived_at: Option<DateTime<Utc>>,
+
+    /// Ed25519 signature over the rest of the record (see [`crate::core::signature`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<RecordSignature>,
 }
 
 impl Constraint {
@@ -85,7 +144,7 @@ impl Constraint {
             .unwrap_or_else(|| Self::generate_id(&params.text, &params.category, &params.r#type));
 
         let constraint = Self {
-            version: 1,
+            version: CURRENT_VERSION,
             id,
             r#type: params.r#type,
             category: params.category,
@@ -95,9 +154,16 @@ impl Constraint {
             author: params.author,
             references: params.references,
             verification: params.verification,
+            depends_on: params.depends_on,
+            supersedes: params.supersedes,
+            conflicts_with: params.conflicts_with,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             validation_status: ValidationStatus::Valid,
+            lifecycle: params.lifecycle,
+            approvals: vec![],
+            archived_at: None,
+            signature: None,
         };
 
         constraint.validate()?;
@@ -113,14 +179,34 @@ impl Constraint {
             ));
         }
 
-        // Validate ID format
-        let id_regex = Regex::new(r"^nt-[0-9a-z]{6}$")
+        // Validate ID format (the prefix itself is workspace-configurable)
+        let id_regex = Regex::new(r"^[a-z]{1,8}-[0-9a-z]{6}$")
             .map_err(|_| ConstraintError::Validation("Invalid regex pattern".to_string()))?;
 
         if !id_regex.is_match(&self.id) {
             return Err(ConstraintError::InvalidIdFormat(self.id.clone()));
         }
 
+        // Validate relationship targets
+        let mut targets = BTreeMap::new();
+        for relation in self.relations() {
+            if !id_regex.is_match(&relation.target) {
+                return Err(ConstraintError::InvalidIdFormat(relation.target));
+            }
+            if relation.target == self.id {
+                return Err(ConstraintError::Validation(format!(
+                    "A constraint cannot {} itself",
+                    relation.kind
+                )));
+            }
+            if let Some(kind) = targets.insert(relation.target.clone(), relation.kind) {
+                return Err(ConstraintError::Validation(format!(
+                    "{} is listed as both {} and {}",
+                    relation.target, kind, relation.kind
+                )));
+            }
+        }
+
         // Validate category format
         let category_regex = Regex::new(r"^[a-z0-9-]+$")
             .map_err(|_| ConstraintError::Validation("Invalid category regex".to_string()))?;
@@ -167,38 +253,254 @@ impl Constraint {
             }
         }
 
+        // Validate verification spec if present
+        if let Some(verification) = &self.verification {
+            verification.validate()?;
+        }
+
         Ok(())
     }
 
     /// Generate a deterministic ID from constraint content
     pub fn generate_id(text: &str, category: &str, r#type: &ConstraintType) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        text.hash(&mut hasher);
-        category.hash(&mut hasher);
-        format!("{:?}", r#type).hash(&mut hasher);
-
-        let hash = hasher.finish();
-        let hash_str = format!("{:x}", hash);
-
-        // Take first 6 characters of hash and ensure they're valid base36
-        let suffix: String = hash_str
-            .chars()
-            .take(6)
-            .map(|c| match c {
-                '0'..='9' => c,
-                'a'..='f' => (b'a' + (c as u8 - b'a')) as char,
-                _ => '0',
+        Self::generate_id_with_prefix(IdGenerator::DEFAULT_PREFIX, text, category, r#type)
+    }
+
+    /// Generate a deterministic ID from constraint content using a custom prefix
+    pub fn generate_id_with_prefix(
+        prefix: &str,
+        text: &str,
+        category: &str,
+        r#type: &ConstraintType,
+    ) -> String {
+        Self::generate_alternate_id(prefix, text, category, r#type, 0)
+    }
+
+    /// Generate the `attempt`-th candidate ID for constraint content
+    ///
+    /// Attempt 0 is the natural ID; later attempts salt the hash with the
+    /// attempt number, giving a deterministic fallback sequence when the
+    /// natural ID is already taken by different content. See
+    /// [`crate::utils::id`] for the algorithm.
+    pub fn generate_alternate_id(
+        prefix: &str,
+        text: &str,
+        category: &str,
+        r#type: &ConstraintType,
+        attempt: u32,
+    ) -> String {
+        IdGenerator::with_prefix(prefix).generate_alternate(
+            text,
+            category,
+            r#type.as_str(),
+            attempt,
+        )
+    }
+
+    /// List the outgoing relationship edges, grouped by kind
+    pub fn relations(&self) -> Vec<Relation> {
+        RelationKind::ALL
+            .into_iter()
+            .flat_map(|kind| {
+                self.relation_targets(kind)
+                    .iter()
+                    .map(move |target| Relation {
+                        source: self.id.clone(),
+                        kind,
+                        target: target.clone(),
+                    })
             })
-            .collect();
+            .collect()
+    }
+
+    /// Get the targets of one kind of relationship
+    pub fn relation_targets(&self, kind: RelationKind) -> &[String] {
+        match kind {
+            RelationKind::DependsOn => &self.depends_on,
+            RelationKind::Supersedes => &self.supersedes,
+            RelationKind::ConflictsWith => &self.conflicts_with,
+        }
+    }
 
-        format!("nt-{}", suffix)
+    /// Point relationships at new IDs, returning whether anything changed
+    pub fn retarget_relations(&mut self, renamed: &HashMap<String, String>) -> bool {
+        let mut changed = false;
+        for targets in [
+            &mut self.depends_on,
+            &mut self.supersedes,
+            &mut self.conflicts_with,
+        ] {
+            for target in targets.iter_mut() {
+                if let Some(new_id) = renamed.get(target) {
+                    *target = new_id.clone();
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Drop relationships whose target matches, returning whether anything changed
+    pub fn remove_relations<F>(&mut self, matches: F) -> bool
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut changed = false;
+        for targets in [
+            &mut self.depends_on,
+            &mut self.supersedes,
+            &mut self.conflicts_with,
+        ] {
+            let before = targets.len();
+            targets.retain(|target| !matches(target));
+            changed |= targets.len() != before;
+        }
+        changed
+    }
+
+    /// Record fields covered by [`Constraint::content_hash`]
+    pub const CONTENT_FIELDS: [&'static str; 10] = [
+        "type",
+        "category",
+        "text",
+        "tags",
+        "priority",
+        "references",
+        "verification",
+        "depends_on",
+        "supersedes",
+        "conflicts_with",
+    ];
+
+    /// Hash of the reviewable content, as lowercase hex SHA-256
+    ///
+    /// Covers everything a reviewer signs off on: type, category, text, tags,
+    /// priority, references, verification and relationships. Bookkeeping
+    /// fields (timestamps, lifecycle, archive state, approvals) are left out,
+    /// so only a change to the content itself invalidates approvals.
+    pub fn content_hash(&self) -> String {
+        #[derive(Serialize)]
+        struct Content<'a> {
+            r#type: ConstraintType,
+            category: &'a str,
+            text: &'a str,
+            tags: &'a [String],
+            priority: &'a Option<String>,
+            references: &'a str,
+            verification: Option<Verification<'a>>,
+            depends_on: &'a [String],
+            supersedes: &'a [String],
+            conflicts_with: &'a [String],
+        }
+
+        // Plain scripts hash as the bare string the pre-spec format stored,
+        // so upgrading old records does not void their approvals
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum Verification<'a> {
+            Script(&'a str),
+            Spec(&'a VerificationSpec),
+        }
+
+        // Struct fields serialize in declaration order, giving a stable encoding
+        let content = serde_json::to_vec(&Content {
+            r#type: self.r#type,
+            category: &self.category,
+            text: &self.text,
+            tags: &self.tags,
+            priority: &self.priority,
+            references: &self.references,
+            verification: self.verification.as_ref().map(|spec| {
+                spec.as_plain_shell()
+                    .map_or(Verification::Spec(spec), Verification::Script)
+            }),
+            depends_on: &self.depends_on,
+            supersedes: &self.supersedes,
+            conflicts_with: &self.conflicts_with,
+        })
+        .expect("constraint content is always serializable");
+
+        Sha256::digest(&content)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Approvals given for the current content
+    pub fn current_approvals(&self) -> Vec<&Approval> {
+        let hash = self.content_hash();
+        self.approvals
+            .iter()
+            .filter(|approval| approval.content_hash == hash)
+            .collect()
+    }
+
+    /// Record a reviewer's sign-off on the current content
+    ///
+    /// Neither the author nor `last_editor`, whoever made the latest change
+    /// to the content, may approve it.
+    pub fn approve(
+        &mut self,
+        reviewer: &str,
+        last_editor: Option<&str>,
+    ) -> Result<(), ConstraintError> {
+        let reviewer = reviewer.trim();
+        if reviewer.is_empty() {
+            return Err(ConstraintError::Validation(
+                "Reviewer cannot be empty".to_string(),
+            ));
+        }
+
+        if reviewer == self.author.trim() {
+            return Err(ConstraintError::Validation(format!(
+                "{} is the author of {} and cannot approve it",
+                reviewer, self.id
+            )));
+        }
+
+        if last_editor.is_some_and(|editor| reviewer == editor.trim()) {
+            return Err(ConstraintError::Validation(format!(
+                "{} made the latest change to {} and cannot approve it",
+                reviewer, self.id
+            )));
+        }
+
+        if self
+            .current_approvals()
+            .iter()
+            .any(|approval| approval.reviewer == reviewer)
+        {
+            return Err(ConstraintError::Validation(format!(
+                "{} has already approved the current content of {}",
+                reviewer, self.id
+            )));
+        }
+
+        self.approvals
+            .push(Approval::new(reviewer, &self.content_hash()));
+        Ok(())
+    }
+
+    /// Check whether two constraints carry the same content (type, category and text)
+    pub fn same_content(&self, other: &Constraint) -> bool {
+        self.r#type == other.r#type && self.category == other.category && self.text == other.text
     }
 
     /// Update the constraint with new data
     pub fn update(&mut self, updates: ConstraintUpdate) -> Result<(), ConstraintError> {
+        // Lifecycle changes must follow the state machine; check before touching anything
+        if let Some(lifecycle) = updates.lifecycle {
+            self.lifecycle.check_transition(lifecycle)?;
+        }
+
+        if let Some(category) = updates.category {
+            self.category = category;
+        }
+
+        if let Some(r#type) = updates.r#type {
+            self.r#type = r#type;
+        }
+
         if let Some(text) = updates.text {
             self.text = text;
         }
@@ -219,10 +521,60 @@ impl Constraint {
             self.verification = verification;
         }
 
+        if let Some(depends_on) = updates.depends_on {
+            self.depends_on = depends_on;
+        }
+
+        if let Some(supersedes) = updates.supersedes {
+            self.supersedes = supersedes;
+        }
+
+        if let Some(conflicts_with) = updates.conflicts_with {
+            self.conflicts_with = conflicts_with;
+        }
+
+        if let Some(lifecycle) = updates.lifecycle {
+            self.lifecycle = lifecycle;
+        }
+
         self.updated_at = Utc::now();
         self.validate()?;
         Ok(())
     }
+
+    /// Check whether the constraint has been archived
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    /// Archive the constraint, hiding it from default views
+    pub fn archive(&mut self) -> Result<(), ConstraintError> {
+        if self.is_archived() {
+            return Err(ConstraintError::Validation(format!(
+                "Constraint {} is already archived",
+                self.id
+            )));
+        }
+
+        let now = Utc::now();
+        self.archived_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Bring an archived constraint back into default views
+    pub fn restore(&mut self) -> Result<(), ConstraintError> {
+        if !self.is_archived() {
+            return Err(ConstraintError::Validation(format!(
+                "Constraint {} is not archived",
+                self.id
+            )));
+        }
+
+        self.archived_at = None;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
 }
 
 /// Parameters for creating a new constraint
@@ -236,17 +588,27 @@ pub struct ConstraintParams {
     pub tags: Vec<String>,
     pub priority: Option<String>,
     pub references: String,
-    pub verification: Option<String>,
+    pub verification: Option<VerificationSpec>,
+    pub depends_on: Vec<String>,
+    pub supersedes: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub lifecycle: Lifecycle,
 }
 
 /// Structure for constraint updates
 #[derive(Debug, Default)]
 pub struct ConstraintUpdate {
+    pub category: Option<String>,
+    pub r#type: Option<ConstraintType>,
     pub text: Option<String>,
     pub tags: Option<Vec<String>>,
     pub priority: Option<Option<String>>,
     pub references: Option<String>,
-    pub verification: Option<Option<String>>,
+    pub verification: Option<Option<VerificationSpec>>,
+    pub depends_on: Option<Vec<String>>,
+    pub supersedes: Option<Vec<String>>,
+    pub conflicts_with: Option<Vec<String>>,
+    pub lifecycle: Option<Lifecycle>,
 }
 
 #[cfg(test)]
@@ -265,10 +627,14 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
-        assert_eq!(constraint.version, 1);
+        assert_eq!(constraint.version, CURRENT_VERSION);
         assert!(constraint.id.starts_with("nt-"));
         assert_eq!(constraint.category, "security");
         assert_eq!(constraint.text, "All passwords must be hashed");
@@ -288,6 +654,89 @@ mod tests {
         assert_eq!(id1, id2);
         assert!(id1.starts_with("nt-"));
         assert_eq!(id1.len(), 9); // nt- + 6 chars
+
+        let custom = Constraint::generate_id_with_prefix(
+            "acme",
+            "test text",
+            "category",
+            &ConstraintType::Must,
+        );
+        assert_eq!(custom, id1.replacen("nt-", "acme-", 1));
+
+        // Alternates are deterministic and differ from the natural ID
+        let alternate = Constraint::generate_alternate_id(
+            "nt",
+            "test text",
+            "category",
+            &ConstraintType::Must,
+            1,
+        );
+        assert_eq!(
+            alternate,
+            Constraint::generate_alternate_id(
+                "nt",
+                "test text",
+                "category",
+                &ConstraintType::Must,
+                1
+            )
+        );
+        assert_ne!(alternate, id1);
+
+        // Delegates to the documented algorithm
+        assert_eq!(
+            id1,
+            IdGenerator::new().generate("test text", "category", "MUST")
+        );
+    }
+
+    #[test]
+    fn test_relation_validation() {
+        let params = || ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Use argon2id".to_string(),
+            author: "author".to_string(),
+            id: Some("nt-self01".to_string()),
+            ..Default::default()
+        };
+
+        let constraint = Constraint::new(ConstraintParams {
+            depends_on: vec!["nt-base01".to_string()],
+            supersedes: vec!["nt-old001".to_string()],
+            ..params()
+        })
+        .unwrap();
+        let relations = constraint.relations();
+        assert_eq!(relations.len(), 2);
+        assert_eq!(relations[0].kind, RelationKind::DependsOn);
+        assert_eq!(relations[1].target, "nt-old001");
+
+        // Self-references, malformed targets and contradictory edges are rejected
+        for params in [
+            ConstraintParams {
+                depends_on: vec!["nt-self01".to_string()],
+                ..params()
+            },
+            ConstraintParams {
+                supersedes: vec!["not an id".to_string()],
+                ..params()
+            },
+            ConstraintParams {
+                depends_on: vec!["nt-base01".to_string()],
+                conflicts_with: vec!["nt-base01".to_string()],
+                ..params()
+            },
+        ] {
+            assert!(Constraint::new(params).is_err());
+        }
+
+        // Retargeting follows renamed IDs
+        let mut constraint = constraint;
+        let renamed = HashMap::from([("nt-base01".to_string(), "nt-base02".to_string())]);
+        assert!(constraint.retarget_relations(&renamed));
+        assert_eq!(constraint.depends_on, vec!["nt-base02".to_string()]);
+        assert!(!constraint.retarget_relations(&renamed));
     }
 
     #[test]
@@ -303,6 +752,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         });
         assert!(result.is_ok());
 
@@ -317,6 +770,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         });
         assert!(matches!(result, Err(ConstraintError::Validation(_))));
 
@@ -331,6 +788,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         });
         assert!(matches!(result, Err(ConstraintError::Validation(_))));
 
@@ -345,6 +806,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         });
         assert!(matches!(result, Err(ConstraintError::Validation(_))));
 
@@ -359,6 +824,10 @@ mod tests {
             priority: Some("P4".to_string()),
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         });
         assert!(matches!(result, Err(ConstraintError::Validation(_))));
 
@@ -374,7 +843,119 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
+        });
+        assert!(matches!(result, Err(ConstraintError::Validation(_))));
+    }
+
+    #[test]
+    fn test_archive_and_restore() {
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Valid text".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!constraint.is_archived());
+        assert!(constraint.restore().is_err());
+
+        constraint.archive().unwrap();
+        assert!(constraint.is_archived());
+        assert!(constraint.archive().is_err());
+
+        // The archive marker survives serialization; live records omit it
+        let json = serde_json::to_value(&constraint).unwrap();
+        assert!(json.get("archived_at").is_some());
+        let loaded: Constraint = serde_json::from_value(json).unwrap();
+        assert!(loaded.is_archived());
+
+        constraint.restore().unwrap();
+        assert!(!constraint.is_archived());
+        assert!(serde_json::to_value(&constraint)
+            .unwrap()
+            .get("archived_at")
+            .is_none());
+    }
+
+    #[test]
+    fn test_update_category_and_type_keeps_id() {
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Should,
+            category: "testing".to_string(),
+            text: "Valid text".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let id = constraint.id.clone();
+
+        constraint
+            .update(ConstraintUpdate {
+                category: Some("security".to_string()),
+                r#type: Some(ConstraintType::Must),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(constraint.id, id);
+        assert_eq!(constraint.category, "security");
+        assert_eq!(constraint.r#type, ConstraintType::Must);
+
+        let result = constraint.update(ConstraintUpdate {
+            category: Some("Not Valid".to_string()),
+            ..Default::default()
         });
         assert!(matches!(result, Err(ConstraintError::Validation(_))));
     }
+
+    #[test]
+    fn test_approvals_bound_to_content() {
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Valid text".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let hash = constraint.content_hash();
+        assert_eq!(hash.len(), 64);
+
+        // Authors cannot approve their own constraints, reviewers only once
+        assert!(constraint.approve("author", None).is_err());
+        constraint.approve("reviewer", None).unwrap();
+        assert!(constraint.approve("reviewer", None).is_err());
+        assert_eq!(constraint.current_approvals().len(), 1);
+
+        // Lifecycle changes leave the content (and approvals) alone
+        constraint
+            .update(ConstraintUpdate {
+                lifecycle: Some(Lifecycle::Deprecated),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(constraint.content_hash(), hash);
+        assert_eq!(constraint.current_approvals().len(), 1);
+
+        // Any content change invalidates them, but they stay on record
+        constraint
+            .update(ConstraintUpdate {
+                text: Some("Changed text".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_ne!(constraint.content_hash(), hash);
+        assert!(constraint.current_approvals().is_empty());
+        assert_eq!(constraint.approvals.len(), 1);
+        constraint.approve("reviewer", None).unwrap();
+        assert_eq!(constraint.current_approvals().len(), 1);
+
+        // Whoever made the latest change cannot sign off on it either
+        assert!(constraint.approve("editor", Some("editor")).is_err());
+        constraint.approve("other", Some("editor")).unwrap();
+    }
 }
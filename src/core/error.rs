@@ -17,9 +17,18 @@ pub enum ConstraintError {
     #[error("Workspace not found: {0}")]
     WorkspaceNotFound(String),
 
+    #[error("Configuration error: {0}")]
+    Config(String),
+
     #[error("Constraint not found: {id}")]
     NotFound { id: String },
 
+    #[error("Ambiguous ID prefix '{prefix}' matches: {}", .candidates.join(", "))]
+    AmbiguousId {
+        prefix: String,
+        candidates: Vec<String>,
+    },
+
     #[error("Invalid constraint type: {0}. Must be one of: MUST, SHALL, SHOULD, MAY, FORBIDDEN (RFC 2119)")]
     InvalidConstraintType(String),
 
@@ -27,9 +36,14 @@ pub enum ConstraintError {
     InvalidIdFormat(String),
 
     #[error("Constraint ID collision: {id} already exists")]
-    #[allow(dead_code)]
     IdCollision { id: String },
 
+    #[error("Duplicate constraint: identical content is already stored as {id}")]
+    Duplicate { id: String },
+
+    #[error("Signature check failed for {id}: {reason}")]
+    Signature { id: String, reason: String },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -61,6 +75,10 @@ pub enum StorageError {
     #[error("File corruption detected: {0}")]
     #[allow(dead_code)]
     Corruption(String),
+
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
 }
 
 /// Version loader specific errors
@@ -81,4 +99,11 @@ pub enum LoaderError {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error("line {line}: {source}")]
+    Line {
+        line: usize,
+        #[source]
+        source: Box<LoaderError>,
+    },
 }
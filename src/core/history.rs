@@ -0,0 +1,234 @@
+//! Revision history for constraints
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+
+/// Fields left out of revision diffs because every write changes them
+const UNTRACKED_FIELDS: &[&str] = &["updated_at", "version", "signature"];
+
+/// Kind of change a revision records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RevisionAction {
+    Created,
+    Updated,
+    Moved,
+    Reidentified,
+    Transitioned,
+    Approved,
+    Archived,
+    Restored,
+    Deleted,
+}
+
+impl std::fmt::Display for RevisionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RevisionAction::Created => write!(f, "created"),
+            RevisionAction::Updated => write!(f, "updated"),
+            RevisionAction::Moved => write!(f, "moved"),
+            RevisionAction::Reidentified => write!(f, "reidentified"),
+            RevisionAction::Transitioned => write!(f, "transitioned"),
+            RevisionAction::Approved => write!(f, "approved"),
+            RevisionAction::Archived => write!(f, "archived"),
+            RevisionAction::Restored => write!(f, "restored"),
+            RevisionAction::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+/// A single recorded change to a constraint
+///
+/// `old` and `new` hold only the fields that changed, keyed by their
+/// serialized field name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    /// ID of the constraint that changed
+    pub constraint_id: String,
+
+    /// Kind of change
+    pub action: RevisionAction,
+
+    /// Who made the change
+    pub actor: String,
+
+    /// When the change was made
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub timestamp: DateTime<Utc>,
+
+    /// Previous values of the changed fields
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub old: BTreeMap<String, Value>,
+
+    /// New values of the changed fields
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub new: BTreeMap<String, Value>,
+}
+
+/// One field-level difference within a revision
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+impl Revision {
+    /// Record the difference between two states of a constraint
+    ///
+    /// `old` is `None` for a newly created constraint and `new` is `None`
+    /// for a deleted one.
+    pub fn between(
+        action: RevisionAction,
+        actor: &str,
+        old: Option<&Constraint>,
+        new: Option<&Constraint>,
+    ) -> Result<Self, ConstraintError> {
+        let constraint_id = new.or(old).map(|c| c.id.clone()).ok_or_else(|| {
+            ConstraintError::Validation("Revision needs a constraint".to_string())
+        })?;
+
+        let (old, new) = diff_fields(&snapshot(old)?, &snapshot(new)?);
+
+        Ok(Self {
+            constraint_id,
+            action,
+            actor: actor.to_string(),
+            // Stored with second precision, so truncate up front
+            timestamp: Utc::now().trunc_subsecs(0),
+            old,
+            new,
+        })
+    }
+
+    /// Check whether the revision records any field change
+    pub fn is_empty(&self) -> bool {
+        self.old.is_empty() && self.new.is_empty()
+    }
+
+    /// Whether the revision changed any field covered by the content hash
+    pub fn changes_content(&self) -> bool {
+        self.old
+            .keys()
+            .chain(self.new.keys())
+            .any(|field| Constraint::CONTENT_FIELDS.contains(&field.as_str()))
+    }
+
+    /// List the changed fields in name order
+    pub fn changes(&self) -> Vec<FieldChange> {
+        let mut fields: Vec<&String> = self.old.keys().chain(self.new.keys()).collect();
+        fields.sort();
+        fields.dedup();
+
+        fields
+            .into_iter()
+            .map(|field| FieldChange {
+                field: field.clone(),
+                old: self.old.get(field).cloned(),
+                new: self.new.get(field).cloned(),
+            })
+            .collect()
+    }
+}
+
+/// Serialize a constraint into a field map, leaving out untracked fields
+fn snapshot(constraint: Option<&Constraint>) -> Result<BTreeMap<String, Value>, ConstraintError> {
+    let Some(constraint) = constraint else {
+        return Ok(BTreeMap::new());
+    };
+
+    match serde_json::to_value(constraint)? {
+        Value::Object(map) => Ok(map
+            .into_iter()
+            .filter(|(field, _)| !UNTRACKED_FIELDS.contains(&field.as_str()))
+            .collect()),
+        _ => Err(ConstraintError::Validation(
+            "Constraint did not serialize to an object".to_string(),
+        )),
+    }
+}
+
+/// Split two field maps into the old and new values of the fields that differ
+fn diff_fields(
+    old: &BTreeMap<String, Value>,
+    new: &BTreeMap<String, Value>,
+) -> (BTreeMap<String, Value>, BTreeMap<String, Value>) {
+    let changed = |field: &String| old.get(field) != new.get(field);
+
+    (
+        old.iter()
+            .filter(|(field, _)| changed(field))
+            .map(|(field, value)| (field.clone(), value.clone()))
+            .collect(),
+        new.iter()
+            .filter(|(field, _)| changed(field))
+            .map(|(field, value)| (field.clone(), value.clone()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType, ConstraintUpdate};
+
+    fn constraint() -> Constraint {
+        Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_revision_records_changed_fields() {
+        let old = constraint();
+        let mut new = old.clone();
+        new.update(ConstraintUpdate {
+            text: Some("Hash passwords with argon2id".to_string()),
+            priority: Some(Some("P1".to_string())),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let revision =
+            Revision::between(RevisionAction::Updated, "reviewer", Some(&old), Some(&new)).unwrap();
+
+        assert_eq!(revision.constraint_id, old.id);
+        assert_eq!(revision.actor, "reviewer");
+
+        let changes = revision.changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, "priority");
+        assert_eq!(changes[0].old, None);
+        assert_eq!(changes[0].new, Some(Value::from("P1")));
+        assert_eq!(changes[1].field, "text");
+        assert_eq!(changes[1].old, Some(Value::from("Hash passwords")));
+    }
+
+    #[test]
+    fn test_delete_revision_and_empty_update() {
+        let old = constraint();
+
+        let revision =
+            Revision::between(RevisionAction::Deleted, "author", Some(&old), None).unwrap();
+        assert!(revision.new.is_empty());
+        assert_eq!(
+            revision.old.get("text"),
+            Some(&Value::from("Hash passwords"))
+        );
+        assert!(!revision.old.contains_key("updated_at"));
+
+        let unchanged =
+            Revision::between(RevisionAction::Updated, "author", Some(&old), Some(&old)).unwrap();
+        assert!(unchanged.is_empty());
+    }
+}
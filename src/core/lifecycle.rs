@@ -0,0 +1,156 @@
+//! Constraint lifecycle states and the transitions allowed between them
+//!
+//! ```text
+//! draft ──> proposed ──> accepted ──> deprecated
+//!   ^          │            │             │
+//!   └──────────┘            └──> superseded <┘
+//! ```
+//!
+//! A proposal can be sent back to draft, and a deprecated constraint can be
+//! reinstated as accepted. Superseded is final.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::ConstraintError;
+
+/// Where a constraint stands in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Lifecycle {
+    Draft,    // Being written; not enforced
+    Proposed, // Up for review; not enforced
+    #[default]
+    Accepted, // In force
+    Deprecated, // Retired but not yet replaced
+    Superseded, // Replaced by another constraint
+}
+
+impl Lifecycle {
+    /// States that can be reached directly from this one
+    pub fn successors(&self) -> &'static [Lifecycle] {
+        match self {
+            Lifecycle::Draft => &[Lifecycle::Proposed],
+            Lifecycle::Proposed => &[Lifecycle::Accepted, Lifecycle::Draft],
+            Lifecycle::Accepted => &[Lifecycle::Deprecated, Lifecycle::Superseded],
+            Lifecycle::Deprecated => &[Lifecycle::Accepted, Lifecycle::Superseded],
+            Lifecycle::Superseded => &[],
+        }
+    }
+
+    /// Check that moving from this state to `next` is allowed
+    pub fn check_transition(&self, next: Lifecycle) -> Result<(), ConstraintError> {
+        if self.successors().contains(&next) {
+            return Ok(());
+        }
+
+        let allowed = if self.successors().is_empty() {
+            "none, it is final".to_string()
+        } else {
+            self.successors()
+                .iter()
+                .map(Lifecycle::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        Err(ConstraintError::Validation(format!(
+            "Cannot move a constraint from {} to {} (allowed: {})",
+            self, next, allowed
+        )))
+    }
+
+    /// Whether constraints in this state are enforced by `validate`
+    pub fn is_enforced(&self) -> bool {
+        *self == Lifecycle::Accepted
+    }
+
+    /// Whether the constraint has been retired (deprecated or superseded)
+    pub fn is_retired(&self) -> bool {
+        matches!(self, Lifecycle::Deprecated | Lifecycle::Superseded)
+    }
+
+    /// Parse a lifecycle state from its name
+    pub fn parse(value: &str) -> Result<Lifecycle, ConstraintError> {
+        match value.to_lowercase().as_str() {
+            "draft" => Ok(Lifecycle::Draft),
+            "proposed" => Ok(Lifecycle::Proposed),
+            "accepted" => Ok(Lifecycle::Accepted),
+            "deprecated" => Ok(Lifecycle::Deprecated),
+            "superseded" => Ok(Lifecycle::Superseded),
+            _ => Err(ConstraintError::Validation(format!(
+                "Unknown lifecycle state '{}' (expected draft, proposed, accepted, deprecated or superseded)",
+                value
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for Lifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lifecycle::Draft => write!(f, "draft"),
+            Lifecycle::Proposed => write!(f, "proposed"),
+            Lifecycle::Accepted => write!(f, "accepted"),
+            Lifecycle::Deprecated => write!(f, "deprecated"),
+            Lifecycle::Superseded => write!(f, "superseded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        assert!(Lifecycle::Draft
+            .check_transition(Lifecycle::Proposed)
+            .is_ok());
+        assert!(Lifecycle::Proposed
+            .check_transition(Lifecycle::Accepted)
+            .is_ok());
+        assert!(Lifecycle::Proposed
+            .check_transition(Lifecycle::Draft)
+            .is_ok());
+        assert!(Lifecycle::Accepted
+            .check_transition(Lifecycle::Deprecated)
+            .is_ok());
+        assert!(Lifecycle::Deprecated
+            .check_transition(Lifecycle::Accepted)
+            .is_ok());
+        assert!(Lifecycle::Deprecated
+            .check_transition(Lifecycle::Superseded)
+            .is_ok());
+
+        // Review cannot be skipped, and superseded is final
+        assert!(Lifecycle::Draft
+            .check_transition(Lifecycle::Accepted)
+            .is_err());
+        assert!(Lifecycle::Accepted
+            .check_transition(Lifecycle::Accepted)
+            .is_err());
+        for next in [
+            Lifecycle::Draft,
+            Lifecycle::Proposed,
+            Lifecycle::Accepted,
+            Lifecycle::Deprecated,
+        ] {
+            assert!(Lifecycle::Superseded.check_transition(next).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for state in [
+            Lifecycle::Draft,
+            Lifecycle::Proposed,
+            Lifecycle::Accepted,
+            Lifecycle::Deprecated,
+            Lifecycle::Superseded,
+        ] {
+            assert_eq!(Lifecycle::parse(&state.to_string()).unwrap(), state);
+        }
+        assert_eq!(Lifecycle::parse("DRAFT").unwrap(), Lifecycle::Draft);
+        assert!(Lifecycle::parse("retired").is_err());
+    }
+}
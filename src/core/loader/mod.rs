@@ -3,6 +3,9 @@
 use crate::core::constraint::Constraint;
 use crate::core::error::LoaderError;
 
+/// Format version written by this build
+pub const CURRENT_VERSION: u32 = 2;
+
 /// Trait for loading and upgrading constraint formats
 pub trait ConstraintLoader: Send + Sync {
     /// Get the version this loader handles
@@ -34,10 +37,11 @@ impl Default for LoaderRegistry {
 impl LoaderRegistry {
     /// Create a new loader registry with all available loaders
     pub fn new() -> Self {
-        let loaders: Vec<Box<dyn ConstraintLoader>> = vec![Box::new(v1::V1Loader)];
+        let loaders: Vec<Box<dyn ConstraintLoader>> =
+            vec![Box::new(v1::V1Loader), Box::new(v2::V2Loader)];
 
         Self {
-            current_version: 1,
+            current_version: CURRENT_VERSION,
             loaders,
         }
     }
@@ -75,6 +79,22 @@ impl LoaderRegistry {
         Ok(constraint)
     }
 
+    /// Load every record of a multi-record JSONL document
+    ///
+    /// Blank lines are skipped; failures carry the 1-based line number of the record.
+    pub fn load_jsonl(&self, data: &[u8]) -> Vec<Result<Constraint, LoaderError>> {
+        data.split(|b| *b == b'\n')
+            .enumerate()
+            .filter(|(_, line)| !line.iter().all(u8::is_ascii_whitespace))
+            .map(|(index, line)| {
+                self.load_constraint(line).map_err(|e| LoaderError::Line {
+                    line: index + 1,
+                    source: Box::new(e),
+                })
+            })
+            .collect()
+    }
+
     /// Upgrade a constraint to the current version
     pub fn upgrade_to_current(
         &self,
@@ -99,3 +119,4 @@ impl LoaderRegistry {
 }
 
 pub mod v1;
+pub mod v2;
@@ -72,6 +72,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         }).unwrap();
 
         let data = serde_json::to_vec(&constraint).unwrap();
@@ -1,10 +1,17 @@
 //! V1 constraint format loader
 
+use serde_json::Value;
+
 use crate::core::constraint::Constraint;
 use crate::core::error::LoaderError;
 use crate::core::loader::ConstraintLoader;
+use crate::core::verification::VerificationSpec;
 
-/// Loader for V1 constraint format (current version)
+/// Loader for the V1 constraint format
+///
+/// V1 stored `verification` as a single shell command string. Loading turns
+/// it into a [`VerificationSpec`] running that script, which is the only
+/// difference from V2.
 pub struct V1Loader;
 
 impl ConstraintLoader for V1Loader {
@@ -17,7 +24,20 @@ impl ConstraintLoader for V1Loader {
     }
 
     fn load(&self, data: &[u8]) -> Result<Constraint, LoaderError> {
-        let constraint: Constraint = serde_json::from_slice(data)?;
+        let mut value: Value = serde_json::from_slice(data)?;
+
+        if let Value::Object(fields) = &mut value {
+            if let Some(Value::String(script)) = fields.get("verification") {
+                let spec = if script.trim().is_empty() {
+                    Value::Null
+                } else {
+                    serde_json::to_value(VerificationSpec::shell(script.as_str()))?
+                };
+                fields.insert("verification".to_string(), spec);
+            }
+        }
+
+        let constraint: Constraint = serde_json::from_value(value)?;
 
         if constraint.version != 1 {
             return Err(LoaderError::VersionMismatch {
@@ -34,8 +54,9 @@ impl ConstraintLoader for V1Loader {
         Ok(constraint)
     }
 
-    fn upgrade(&self, constraint: Constraint) -> Result<Constraint, LoaderError> {
-        // V1 is the current version, no upgrade needed
+    fn upgrade(&self, mut constraint: Constraint) -> Result<Constraint, LoaderError> {
+        // Verification strings were already converted while loading
+        constraint.version = 2;
         Ok(constraint)
     }
 }
@@ -58,7 +79,7 @@ mod tests {
         let loader = V1Loader;
 
         // Create a valid V1 constraint JSON
-        let constraint = Constraint::new(crate::core::constraint::ConstraintParams {
+        let mut constraint = Constraint::new(crate::core::constraint::ConstraintParams {
             r#type: ConstraintType::Must,
             category: "test".to_string(),
             text: "Test constraint".to_string(),
@@ -68,8 +89,13 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
+        constraint.version = 1;
 
         let data = serde_json::to_vec(&constraint).unwrap();
         let loaded = loader.load(&data).unwrap();
@@ -94,6 +120,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -116,7 +146,7 @@ mod tests {
     fn test_v1_loader_upgrade() {
         let loader = V1Loader;
 
-        let constraint = Constraint::new(crate::core::constraint::ConstraintParams {
+        let mut constraint = Constraint::new(crate::core::constraint::ConstraintParams {
             r#type: ConstraintType::Must,
             category: "test".to_string(),
             text: "Test constraint".to_string(),
@@ -126,12 +156,35 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
+        constraint.version = 1;
 
-        // V1 upgrade should return the same constraint
+        // V1 upgrade only bumps the version
         let upgraded = loader.upgrade(constraint.clone()).unwrap();
         assert_eq!(upgraded.id, constraint.id);
-        assert_eq!(upgraded.version, constraint.version);
+        assert_eq!(upgraded.version, 2);
+    }
+
+    #[test]
+    fn test_v1_verification_string_becomes_spec() {
+        let record = br#"{"version":1,"id":"nt-test01","type":"MUST","category":"test","text":"Test constraint","author":"test-author","verification":"cargo test","created_at":1735689600,"updated_at":1735689600,"validation_status":"valid"}"#;
+
+        let loaded = V1Loader.load(record).unwrap();
+        assert_eq!(
+            loaded.verification,
+            Some(VerificationSpec::shell("cargo test"))
+        );
+
+        // The registry upgrades the record to the current format
+        let upgraded = crate::core::loader::LoaderRegistry::new()
+            .load_constraint(record)
+            .unwrap();
+        assert_eq!(upgraded.version, crate::core::loader::CURRENT_VERSION);
+        assert_eq!(upgraded.verification, loaded.verification);
     }
 }
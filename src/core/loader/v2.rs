@@ -0,0 +1,84 @@
+//! V2 constraint format loader
+
+use crate::core::constraint::Constraint;
+use crate::core::error::LoaderError;
+use crate::core::loader::ConstraintLoader;
+
+/// Loader for V2 constraint format (current version)
+///
+/// V2 stores `verification` as a structured
+/// [`VerificationSpec`](crate::core::verification::VerificationSpec).
+pub struct V2Loader;
+
+impl ConstraintLoader for V2Loader {
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn can_load(&self, version: u32) -> bool {
+        version == 2
+    }
+
+    fn load(&self, data: &[u8]) -> Result<Constraint, LoaderError> {
+        let constraint: Constraint = serde_json::from_slice(data)?;
+
+        if constraint.version != 2 {
+            return Err(LoaderError::VersionMismatch {
+                expected: 2,
+                found: constraint.version,
+            });
+        }
+
+        // Validate the loaded constraint
+        constraint
+            .validate()
+            .map_err(|e| LoaderError::Validation(e.to_string()))?;
+
+        Ok(constraint)
+    }
+
+    fn upgrade(&self, constraint: Constraint) -> Result<Constraint, LoaderError> {
+        // V2 is the current version, no upgrade needed
+        Ok(constraint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::core::verification::VerificationSpec;
+
+    #[test]
+    fn test_v2_loader_round_trip() {
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "test".to_string(),
+            text: "Test constraint".to_string(),
+            author: "test-author".to_string(),
+            verification: Some(VerificationSpec {
+                argv: vec!["cargo".to_string(), "test".to_string()],
+                exit_codes: vec![0, 101],
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let data = serde_json::to_vec(&constraint).unwrap();
+        let loaded = V2Loader.load(&data).unwrap();
+        assert_eq!(loaded.verification, constraint.verification);
+
+        // V1 records are left to the V1 loader
+        assert!(!V2Loader.can_load(1));
+        let mut old = constraint;
+        old.version = 1;
+        assert!(matches!(
+            V2Loader.load(&serde_json::to_vec(&old).unwrap()),
+            Err(LoaderError::VersionMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+}
@@ -1,8 +1,15 @@
 //! Core data models and business logic for the Newton Constraints CLI tool
 
+pub mod alias;
+pub mod approval;
 pub mod constraint;
 pub mod error;
+pub mod history;
+pub mod lifecycle;
 pub mod loader;
+pub mod relation;
+pub mod signature;
+pub mod verification;
 
 #[allow(unused_imports)]
 pub use constraint::*;
@@ -0,0 +1,47 @@
+//! Typed relationships between constraints
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of edge from one constraint to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+    /// The constraint refines or builds on the target
+    DependsOn,
+    /// The constraint replaces the (usually deprecated) target
+    Supersedes,
+    /// The constraint cannot be satisfied together with the target
+    ConflictsWith,
+}
+
+impl RelationKind {
+    /// Every relation kind, in display order
+    pub const ALL: [RelationKind; 3] = [
+        RelationKind::DependsOn,
+        RelationKind::Supersedes,
+        RelationKind::ConflictsWith,
+    ];
+}
+
+impl std::fmt::Display for RelationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelationKind::DependsOn => write!(f, "depends-on"),
+            RelationKind::Supersedes => write!(f, "supersedes"),
+            RelationKind::ConflictsWith => write!(f, "conflicts-with"),
+        }
+    }
+}
+
+/// A single edge between two constraints
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Relation {
+    /// The constraint the edge starts from
+    pub source: String,
+
+    /// What the edge means
+    pub kind: RelationKind,
+
+    /// The constraint the edge points to
+    pub target: String,
+}
@@ -0,0 +1,276 @@
+//! Ed25519 signatures over constraint records
+//!
+//! A record is signed over its canonical serialization: the stored JSON form
+//! with the `signature`, `version` and `validation_status` fields removed,
+//! object keys sorted and no whitespace. Every other field is covered, so
+//! editing a record by hand (or with a tool that does not hold a signing key)
+//! leaves a signature that no longer verifies. The format version is left out
+//! because loading upgrades it in memory, and the validation status because
+//! `validate` records it on every run. For the same reason a plain shell
+//! verification is serialized as the bare script string V1 stored, so records
+//! signed before the upgrade to structured verification specs still verify.
+
+use std::collections::HashMap;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+
+/// A signature stored alongside a constraint record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordSignature {
+    /// Identifier of the signing key (see [`key_id`])
+    pub key_id: String,
+
+    /// Hex-encoded ed25519 signature over the canonical serialization
+    pub value: String,
+}
+
+/// Outcome of checking a record's signature against the trusted keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum SignatureStatus {
+    /// Signed by a trusted key and unchanged since
+    Valid { key_id: String },
+    /// No signature present
+    Unsigned,
+    /// Signed, but the record changed afterwards (or the signature is malformed)
+    Tampered { key_id: String },
+    /// Signed by a key that is not trusted in this workspace
+    UnknownKey { key_id: String },
+}
+
+impl SignatureStatus {
+    /// Whether the record carries a valid signature from a trusted key
+    pub fn is_valid(&self) -> bool {
+        matches!(self, SignatureStatus::Valid { .. })
+    }
+}
+
+impl std::fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignatureStatus::Valid { key_id } => write!(f, "signed by {}", key_id),
+            SignatureStatus::Unsigned => write!(f, "unsigned"),
+            SignatureStatus::Tampered { key_id } => {
+                write!(f, "modified after it was signed by {}", key_id)
+            }
+            SignatureStatus::UnknownKey { key_id } => {
+                write!(f, "signed by untrusted key {}", key_id)
+            }
+        }
+    }
+}
+
+/// Short identifier of a public key: the first 8 bytes of its SHA-256, in hex
+pub fn key_id(key: &VerifyingKey) -> String {
+    hex::encode(&Sha256::digest(key.as_bytes())[..8])
+}
+
+/// Serialize a constraint in the canonical form that signatures cover
+pub fn canonical_bytes(constraint: &Constraint) -> Result<Vec<u8>, ConstraintError> {
+    let mut value = serde_json::to_value(constraint)?;
+    if let Value::Object(fields) = &mut value {
+        fields.remove("signature");
+        fields.remove("version");
+        fields.remove("validation_status");
+
+        let script = constraint
+            .verification
+            .as_ref()
+            .and_then(|spec| spec.as_plain_shell());
+        if let Some(script) = script {
+            fields.insert("verification".to_string(), Value::from(script));
+        }
+    }
+
+    let mut out = String::new();
+    write_canonical(&value, &mut out)?;
+    Ok(out.into_bytes())
+}
+
+/// Write a JSON value with object keys sorted, independent of map ordering
+fn write_canonical(value: &Value, out: &mut String) -> Result<(), ConstraintError> {
+    match value {
+        Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key)?);
+                out.push(':');
+                write_canonical(&fields[key], out)?;
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&serde_json::to_string(scalar)?),
+    }
+    Ok(())
+}
+
+/// Sign a constraint's canonical serialization
+pub fn sign(constraint: &Constraint, key: &SigningKey) -> Result<RecordSignature, ConstraintError> {
+    let signature = key.sign(&canonical_bytes(constraint)?);
+    Ok(RecordSignature {
+        key_id: key_id(&key.verifying_key()),
+        value: hex::encode(signature.to_bytes()),
+    })
+}
+
+/// Check a constraint's signature against a set of trusted keys (by key ID)
+pub fn verify(constraint: &Constraint, trusted: &HashMap<String, VerifyingKey>) -> SignatureStatus {
+    let Some(signature) = &constraint.signature else {
+        return SignatureStatus::Unsigned;
+    };
+    let key_id = signature.key_id.clone();
+
+    let Some(key) = trusted.get(&key_id) else {
+        return SignatureStatus::UnknownKey { key_id };
+    };
+
+    let valid = hex::decode(&signature.value)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .zip(canonical_bytes(constraint).ok())
+        .is_some_and(|(signature, message)| key.verify(&message, &signature).is_ok());
+
+    if valid {
+        SignatureStatus::Valid { key_id }
+    } else {
+        SignatureStatus::Tampered { key_id }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType, ValidationStatus};
+    use crate::core::loader::LoaderRegistry;
+    use crate::core::verification::VerificationSpec;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trusted = HashMap::from([(key_id(&key.verifying_key()), key.verifying_key())]);
+
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(verify(&constraint, &trusted), SignatureStatus::Unsigned);
+
+        constraint.signature = Some(sign(&constraint, &key).unwrap());
+        assert!(verify(&constraint, &trusted).is_valid());
+
+        // The signature survives a round trip through the stored form
+        let stored: Constraint =
+            serde_json::from_slice(&serde_json::to_vec(&constraint).unwrap()).unwrap();
+        assert!(verify(&stored, &trusted).is_valid());
+
+        // Neither do format upgrades or a recorded validation outcome
+        let mut upgraded = stored.clone();
+        upgraded.version += 1;
+        upgraded.validation_status = ValidationStatus::Invalid;
+        assert!(verify(&upgraded, &trusted).is_valid());
+
+        // Any edit breaks it
+        let mut edited = stored.clone();
+        edited.text = "Store passwords".to_string();
+        assert!(matches!(
+            verify(&edited, &trusted),
+            SignatureStatus::Tampered { .. }
+        ));
+
+        // Keys that are not trusted are reported as such
+        assert!(matches!(
+            verify(&stored, &HashMap::new()),
+            SignatureStatus::UnknownKey { .. }
+        ));
+    }
+
+    #[test]
+    fn test_v1_signature_survives_upgrade() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trusted = HashMap::from([(key_id(&key.verifying_key()), key.verifying_key())]);
+
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "testing".to_string(),
+            text: "Tests pass".to_string(),
+            author: "author".to_string(),
+            verification: Some(VerificationSpec::shell("cargo test")),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // A V1 record, signed over its stored form as V1 builds did
+        let mut record = serde_json::to_value(&constraint).unwrap();
+        record["version"] = Value::from(1);
+        record["verification"] = Value::from("cargo test");
+        let mut signed_fields = record.clone();
+        if let Value::Object(fields) = &mut signed_fields {
+            fields.remove("version");
+            fields.remove("validation_status");
+        }
+        let mut message = String::new();
+        write_canonical(&signed_fields, &mut message).unwrap();
+        record["signature"] = serde_json::to_value(RecordSignature {
+            key_id: key_id(&key.verifying_key()),
+            value: hex::encode(key.sign(message.as_bytes()).to_bytes()),
+        })
+        .unwrap();
+
+        // Loading upgrades it to a structured spec; the signature still holds
+        let loaded = LoaderRegistry::new()
+            .load_constraint(&serde_json::to_vec(&record).unwrap())
+            .unwrap();
+        assert_eq!(loaded.version, 2);
+        assert_eq!(
+            loaded.verification,
+            Some(VerificationSpec::shell("cargo test"))
+        );
+        assert!(verify(&loaded, &trusted).is_valid());
+
+        // Specs the V1 format could not express are signed as structured
+        let mut extended = loaded.clone();
+        extended.verification = Some(VerificationSpec {
+            timeout_secs: Some(60),
+            ..VerificationSpec::shell("cargo test")
+        });
+        assert!(matches!(
+            verify(&extended, &trusted),
+            SignatureStatus::Tampered { .. }
+        ));
+    }
+
+    #[test]
+    fn test_canonical_bytes_sort_keys() {
+        let value: Value =
+            serde_json::from_str(r#"{"b":1,"a":{"d":[2,{"f":3,"e":4}],"c":"x"}}"#).unwrap();
+        let mut out = String::new();
+        write_canonical(&value, &mut out).unwrap();
+        assert_eq!(out, r#"{"a":{"c":"x","d":[2,{"e":4,"f":3}]},"b":1}"#);
+    }
+}
@@ -0,0 +1,291 @@
+//! Structured verification specs
+//!
+//! A verification is either an argv vector, run directly, or a shell script,
+//! run with `sh -c`. Either form may set a working directory (relative to the
+//! project directory holding `.newton`), extra environment variables, a
+//! timeout, the exit codes that count as success and regular expressions the
+//! command's stdout and stderr must match. Declaring input globs lets
+//! `validate` reuse an earlier result while none of the inputs change.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::ConstraintError;
+
+/// How to check that a constraint holds
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VerificationSpec {
+    /// Program and arguments, run without a shell
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub argv: Vec<String>,
+
+    /// Script passed to `sh -c`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub shell: Option<String>,
+
+    /// Working directory, relative to the project directory
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cwd: Option<String>,
+
+    /// Variables added to the inherited environment
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Seconds the command may run
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timeout_secs: Option<u64>,
+
+    /// Exit codes that count as success (only 0 when empty)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub exit_codes: Vec<i32>,
+
+    /// Pattern stdout must match
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stdout_regex: Option<String>,
+
+    /// Pattern stderr must match
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stderr_regex: Option<String>,
+
+    /// Globs (relative to the project directory) of the files the result depends on
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub inputs: Vec<String>,
+
+    /// Never run alongside other verifications (for checks that share state)
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub serial: bool,
+}
+
+impl VerificationSpec {
+    /// A spec that runs a shell script with default settings
+    pub fn shell(script: impl Into<String>) -> Self {
+        Self {
+            shell: Some(script.into()),
+            ..Default::default()
+        }
+    }
+
+    /// The script, if this is a plain shell script with default settings
+    ///
+    /// This is exactly what the string-only verification format could hold.
+    pub fn as_plain_shell(&self) -> Option<&str> {
+        let plain = Self {
+            shell: self.shell.clone(),
+            ..Default::default()
+        };
+        if *self == plain {
+            self.shell.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// Check that the spec is complete and its patterns compile
+    pub fn validate(&self) -> Result<(), ConstraintError> {
+        let invalid = |message: &str| {
+            Err(ConstraintError::Validation(format!(
+                "Invalid verification: {}",
+                message
+            )))
+        };
+
+        match (&self.shell, self.argv.first()) {
+            (Some(_), Some(_)) => return invalid("set either argv or shell, not both"),
+            (None, None) => return invalid("one of argv or shell is required"),
+            (Some(script), None) if script.trim().is_empty() => {
+                return invalid("shell script cannot be empty")
+            }
+            (None, Some(program)) if program.is_empty() => {
+                return invalid("argv program cannot be empty")
+            }
+            _ => {}
+        }
+
+        if let Some(cwd) = &self.cwd {
+            if escapes_project(cwd) {
+                return invalid("cwd must be a relative path inside the project directory");
+            }
+        }
+
+        for input in &self.inputs {
+            if escapes_project(input) {
+                return invalid(&format!(
+                    "input '{}' must be a relative glob inside the project directory",
+                    input
+                ));
+            }
+            if let Err(e) = glob::Pattern::new(input) {
+                return invalid(&format!("input '{}' is not a valid glob: {}", input, e));
+            }
+        }
+
+        if self.timeout_secs == Some(0) {
+            return invalid("timeout_secs must be at least 1");
+        }
+
+        for (stream, pattern) in [
+            ("stdout", &self.stdout_regex),
+            ("stderr", &self.stderr_regex),
+        ] {
+            if let Some(pattern) = pattern {
+                if let Err(e) = Regex::new(pattern) {
+                    return invalid(&format!("{}_regex does not compile: {}", stream, e));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Directory the command runs in, given the project directory
+    pub fn working_dir(&self, project_dir: &Path) -> PathBuf {
+        match &self.cwd {
+            Some(cwd) => project_dir.join(cwd),
+            None => project_dir.to_path_buf(),
+        }
+    }
+
+    /// Whether an exit code counts as success
+    pub fn accepts_exit_code(&self, code: i32) -> bool {
+        if self.exit_codes.is_empty() {
+            code == 0
+        } else {
+            self.exit_codes.contains(&code)
+        }
+    }
+}
+
+/// Whether a relative path could point outside the project directory
+fn escapes_project(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+impl std::fmt::Display for VerificationSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.shell {
+            Some(script) => write!(f, "{}", script)?,
+            None => {
+                let words: Vec<String> = self
+                    .argv
+                    .iter()
+                    .map(|arg| {
+                        if arg.is_empty() || arg.contains(char::is_whitespace) {
+                            format!("{:?}", arg)
+                        } else {
+                            arg.clone()
+                        }
+                    })
+                    .collect();
+                write!(f, "{}", words.join(" "))?;
+            }
+        }
+
+        let mut settings = vec![];
+        if let Some(cwd) = &self.cwd {
+            settings.push(format!("cwd {}", cwd));
+        }
+        if !self.env.is_empty() {
+            let names: Vec<&str> = self.env.keys().map(String::as_str).collect();
+            settings.push(format!("env {}", names.join(",")));
+        }
+        if let Some(timeout) = self.timeout_secs {
+            settings.push(format!("timeout {}s", timeout));
+        }
+        if !self.exit_codes.is_empty() {
+            let codes: Vec<String> = self.exit_codes.iter().map(i32::to_string).collect();
+            settings.push(format!("exit {}", codes.join(",")));
+        }
+        if let Some(pattern) = &self.stdout_regex {
+            settings.push(format!("stdout =~ /{}/", pattern));
+        }
+        if let Some(pattern) = &self.stderr_regex {
+            settings.push(format!("stderr =~ /{}/", pattern));
+        }
+        if !self.inputs.is_empty() {
+            settings.push(format!("inputs {}", self.inputs.join(",")));
+        }
+        if self.serial {
+            settings.push("serial".to_string());
+        }
+        if !settings.is_empty() {
+            write!(f, " [{}]", settings.join("; "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_spec() {
+        assert!(VerificationSpec::shell("cargo test").validate().is_ok());
+
+        let argv = VerificationSpec {
+            argv: vec!["cargo".to_string(), "test".to_string()],
+            cwd: Some("crates/core".to_string()),
+            stdout_regex: Some(r"test result: ok\.".to_string()),
+            ..Default::default()
+        };
+        assert!(argv.validate().is_ok());
+        assert!(VerificationSpec::default().validate().is_err());
+        assert!(VerificationSpec {
+            shell: Some("true".to_string()),
+            ..argv.clone()
+        }
+        .validate()
+        .is_err());
+
+        for cwd in ["../elsewhere", "/tmp"] {
+            let spec = VerificationSpec {
+                cwd: Some(cwd.to_string()),
+                ..VerificationSpec::shell("true")
+            };
+            assert!(spec.validate().is_err(), "{} should be rejected", cwd);
+        }
+
+        let outside = VerificationSpec {
+            inputs: vec!["src/**/*.rs".to_string(), "../shared/*.toml".to_string()],
+            ..VerificationSpec::shell("true")
+        };
+        assert!(outside.validate().is_err());
+
+        let bad_regex = VerificationSpec {
+            stderr_regex: Some("(".to_string()),
+            ..VerificationSpec::shell("true")
+        };
+        assert!(bad_regex.validate().is_err());
+    }
+
+    #[test]
+    fn test_plain_shell_and_exit_codes() {
+        let plain = VerificationSpec::shell("make check");
+        assert_eq!(plain.as_plain_shell(), Some("make check"));
+        assert!(plain.accepts_exit_code(0));
+        assert!(!plain.accepts_exit_code(1));
+        assert_eq!(plain.to_string(), "make check");
+
+        let custom = VerificationSpec {
+            exit_codes: vec![0, 3],
+            timeout_secs: Some(30),
+            ..plain
+        };
+        assert_eq!(custom.as_plain_shell(), None);
+        assert!(custom.accepts_exit_code(3));
+        assert_eq!(custom.to_string(), "make check [timeout 30s; exit 0,3]");
+
+        // The stored form omits defaults
+        assert_eq!(
+            serde_json::to_string(&VerificationSpec::shell("true")).unwrap(),
+            r#"{"shell":"true"}"#
+        );
+    }
+}
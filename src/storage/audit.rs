@@ -0,0 +1,445 @@
+//! Append-only, hash-chained audit log kept in `.newton/audit.jsonl`
+//!
+//! Each line is one [`AuditEntry`] carrying the SHA-256 of the previous line
+//! (64 zeros for the first entry). Rewriting or removing any entry changes
+//! its hash and breaks the link from the entry after it, which
+//! [`AuditLog::verify`] reports. The hash of the last entry (the head) can be
+//! recorded elsewhere to also detect entries being cut off the end.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, SubsecRound, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::core::alias::Alias;
+use crate::core::constraint::{Constraint, ValidationStatus};
+use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
+use crate::storage::ConstraintStore;
+
+/// `prev_hash` of the first entry in a log
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One event in the audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 1
+    pub seq: u64,
+
+    /// When the event happened
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub timestamp: DateTime<Utc>,
+
+    /// Who caused it
+    pub actor: String,
+
+    /// What happened (a revision action such as `created`, or `validated`)
+    pub action: String,
+
+    /// Constraint the event concerns, if it concerns a single one
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub constraint_id: Option<String>,
+
+    /// Event-specific data
+    #[serde(skip_serializing_if = "Value::is_null", default)]
+    pub details: Value,
+
+    /// SHA-256 of the previous line, as lowercase hex
+    pub prev_hash: String,
+}
+
+/// The first entry whose link to its predecessor does not hold
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BrokenLink {
+    /// 1-based line number in the log file
+    pub line: usize,
+
+    /// Sequence number of the entry, when it could be read
+    pub seq: Option<u64>,
+
+    /// What is wrong with it
+    pub reason: String,
+}
+
+/// Outcome of walking the chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainReport {
+    /// Number of entries that chain correctly before the first broken link
+    pub entries: usize,
+
+    /// Hash of the last intact entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head: Option<String>,
+
+    /// First broken link, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken: Option<BrokenLink>,
+}
+
+/// Handle on an audit log file
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Use the audit log at the given path (created on first append)
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append an event, chaining it to the current last entry
+    pub fn append(
+        &self,
+        actor: &str,
+        action: &str,
+        constraint_id: Option<&str>,
+        details: Value,
+    ) -> Result<AuditEntry, ConstraintError> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock_exclusive()?;
+
+        // Read under the lock so concurrent writers cannot fork the chain
+        let mut data = String::new();
+        file.seek(SeekFrom::Start(0))?;
+        file.read_to_string(&mut data)?;
+
+        let (seq, prev_hash) = match data.lines().rfind(|line| !line.trim().is_empty()) {
+            Some(last) => {
+                let last_seq = serde_json::from_str::<AuditEntry>(last)
+                    .map(|entry| entry.seq)
+                    .unwrap_or_else(|_| data.lines().count() as u64);
+                (last_seq + 1, hash_line(last))
+            }
+            None => (1, GENESIS_HASH.to_string()),
+        };
+
+        let entry = AuditEntry {
+            seq,
+            timestamp: Utc::now().trunc_subsecs(0),
+            actor: actor.to_string(),
+            action: action.to_string(),
+            constraint_id: constraint_id.map(str::to_string),
+            details,
+            prev_hash,
+        };
+
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+
+        // Lock is released when the file is dropped
+        drop(file);
+        Ok(entry)
+    }
+
+    /// Record a constraint revision
+    pub fn append_revision(&self, revision: &Revision) -> Result<AuditEntry, ConstraintError> {
+        self.append(
+            &revision.actor,
+            &revision.action.to_string(),
+            Some(&revision.constraint_id),
+            serde_json::json!({ "old": revision.old, "new": revision.new }),
+        )
+    }
+
+    /// Walk the chain from the start and report the first broken link
+    pub fn verify(&self) -> Result<ChainReport, ConstraintError> {
+        let mut report = ChainReport {
+            entries: 0,
+            head: None,
+            broken: None,
+        };
+        if !self.path.exists() {
+            return Ok(report);
+        }
+
+        let data = std::fs::read_to_string(&self.path)?;
+        let mut expected_prev = GENESIS_HASH.to_string();
+
+        for (index, line) in data.lines().enumerate() {
+            let line_number = index + 1;
+            let broken = |seq, reason: String| BrokenLink {
+                line: line_number,
+                seq,
+                reason,
+            };
+
+            let entry = match serde_json::from_str::<AuditEntry>(line) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    report.broken = Some(broken(None, format!("not a valid entry: {}", e)));
+                    break;
+                }
+            };
+
+            if entry.prev_hash != expected_prev {
+                report.broken = Some(broken(
+                    Some(entry.seq),
+                    format!(
+                        "recorded previous-entry hash {} does not match the expected {}; \
+                         an earlier entry was altered, removed or inserted",
+                        entry.prev_hash, expected_prev
+                    ),
+                ));
+                break;
+            }
+
+            if entry.seq != line_number as u64 {
+                report.broken = Some(broken(
+                    Some(entry.seq),
+                    format!("expected sequence number {}", line_number),
+                ));
+                break;
+            }
+
+            expected_prev = hash_line(line);
+            report.entries += 1;
+            report.head = Some(expected_prev.clone());
+        }
+
+        Ok(report)
+    }
+}
+
+/// SHA-256 of one log line (without its newline), as lowercase hex
+fn hash_line(line: &str) -> String {
+    hex::encode(Sha256::digest(line.as_bytes()))
+}
+
+/// Store that records every revision and validation status change in the audit log
+///
+/// Revisions are what every mutating command appends, so wrapping the store
+/// covers add, patch, move, delete, restore, lifecycle changes and approvals
+/// without each command having to remember the audit log. Entries are
+/// appended before the revision or status reaches the inner store, and
+/// commands append a change's revision before writing the change itself, so a
+/// change whose entry cannot be logged is never made. Record writes,
+/// deletions, relocations and aliases are not logged on their own, as each
+/// comes with a revision; commands that change records without one (`sign`
+/// and `storage convert`) log their own entries.
+pub struct AuditedStore {
+    inner: Box<dyn ConstraintStore>,
+    log: AuditLog,
+    actor: String,
+}
+
+impl AuditedStore {
+    /// Wrap a store, auditing its writes into `log`
+    ///
+    /// `actor` is recorded for writes that carry no actor of their own, such
+    /// as validation statuses.
+    pub fn new(inner: Box<dyn ConstraintStore>, log: AuditLog, actor: String) -> Self {
+        Self { inner, log, actor }
+    }
+}
+
+impl ConstraintStore for AuditedStore {
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        self.inner.write_constraint(constraint)
+    }
+
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        self.inner.read_constraint_by_id(id)
+    }
+
+    fn read_category_constraints(
+        &self,
+        category: &str,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        self.inner.read_category_constraints(category)
+    }
+
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
+        self.inner.read_all_constraints()
+    }
+
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        self.inner.delete_constraint(category, id)
+    }
+
+    fn relocate_constraint(
+        &self,
+        constraint: &Constraint,
+        old_category: &str,
+    ) -> Result<(), ConstraintError> {
+        self.inner.relocate_constraint(constraint, old_category)
+    }
+
+    fn record_validation_status(
+        &self,
+        constraint: &Constraint,
+        status: ValidationStatus,
+    ) -> Result<(), ConstraintError> {
+        self.log.append(
+            &self.actor,
+            "status-recorded",
+            Some(&constraint.id),
+            serde_json::json!({ "old": constraint.validation_status, "new": status }),
+        )?;
+        self.inner.record_validation_status(constraint, status)
+    }
+
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError> {
+        self.log.append_revision(revision)?;
+        self.inner.append_revision(revision)
+    }
+
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError> {
+        self.inner.read_history(id)
+    }
+
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError> {
+        self.inner.read_all_history()
+    }
+
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError> {
+        self.inner.write_alias(alias)
+    }
+
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError> {
+        self.inner.read_aliases()
+    }
+
+    fn search_constraints(
+        &self,
+        query: &str,
+        category_filter: Option<&str>,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        self.inner.search_constraints(query, category_filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::core::history::RevisionAction;
+    use crate::storage::MemoryStore;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_audited_store_chains_revisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = AuditLog::new(temp_dir.path().join("audit.jsonl"));
+        let store = AuditedStore::new(Box::new(MemoryStore::new()), log.clone(), "ci".to_string());
+
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        store.write_constraint(&constraint).unwrap();
+        store
+            .append_revision(
+                &Revision::between(RevisionAction::Created, "author", None, Some(&constraint))
+                    .unwrap(),
+            )
+            .unwrap();
+        store
+            .record_validation_status(&constraint, ValidationStatus::Invalid)
+            .unwrap();
+        log.append("ci", "validated", None, serde_json::json!({ "total": 1 }))
+            .unwrap();
+
+        let report = log.verify().unwrap();
+        assert_eq!(report.entries, 3);
+        assert!(report.broken.is_none());
+
+        let data = std::fs::read_to_string(temp_dir.path().join("audit.jsonl")).unwrap();
+        let entries: Vec<AuditEntry> = data
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+        assert_eq!(entries[0].action, "created");
+        assert_eq!(
+            entries[0].constraint_id.as_deref(),
+            Some(constraint.id.as_str())
+        );
+        assert_eq!(entries[1].action, "status-recorded");
+        assert_eq!(entries[1].actor, "ci");
+        assert_eq!(entries[1].details["new"], "invalid");
+    }
+
+    #[test]
+    fn test_audited_store_refuses_unlogged_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let inner = MemoryStore::new();
+        inner.write_constraint(&constraint).unwrap();
+
+        // A directory where the log file should be cannot be appended to
+        let path = temp_dir.path().join("audit.jsonl");
+        std::fs::create_dir(&path).unwrap();
+        let store = AuditedStore::new(Box::new(inner), AuditLog::new(path), "ci".to_string());
+
+        let revision =
+            Revision::between(RevisionAction::Created, "author", None, Some(&constraint)).unwrap();
+
+        assert!(store.append_revision(&revision).is_err());
+        assert!(store.read_history(&constraint.id).unwrap().is_empty());
+        assert!(store
+            .record_validation_status(&constraint, ValidationStatus::Invalid)
+            .is_err());
+        assert_eq!(
+            store
+                .read_constraint_by_id(&constraint.id)
+                .unwrap()
+                .validation_status,
+            constraint.validation_status
+        );
+    }
+
+    #[test]
+    fn test_verify_pinpoints_first_broken_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit.jsonl");
+        let log = AuditLog::new(path.clone());
+        for actor in ["alice", "bob", "carol"] {
+            log.append(actor, "validated", None, Value::Null).unwrap();
+        }
+        let intact = log.verify().unwrap();
+        assert_eq!(intact.entries, 3);
+
+        // Rewriting the second entry breaks the link from the third
+        let data = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, data.replacen("\"bob\"", "\"mallory\"", 1)).unwrap();
+        let report = log.verify().unwrap();
+        assert_eq!(report.entries, 2);
+        let broken = report.broken.unwrap();
+        assert_eq!(broken.line, 3);
+        assert_eq!(broken.seq, Some(3));
+
+        // Appending after a break keeps chaining from the last line
+        log.append("dave", "validated", None, Value::Null).unwrap();
+        assert_eq!(log.verify().unwrap().broken.unwrap().line, 3);
+
+        // Dropping an entry is caught too
+        let lines: Vec<&str> = data.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert_eq!(log.verify().unwrap().broken.unwrap().line, 2);
+    }
+}
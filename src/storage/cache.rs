@@ -0,0 +1,219 @@
+//! Cached verification results kept in `.newton/cache`
+//!
+//! A result is stored per constraint together with the fingerprint of what
+//! produced it: the verification spec (command, working directory,
+//! environment and assertions) and the SHA-256 of every file matched by its
+//! input globs. A later run with the same fingerprint can reuse the result
+//! instead of running the command again.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::core::error::ConstraintError;
+use crate::core::verification::VerificationSpec;
+
+/// A verification result as recorded in the cache
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedVerification {
+    /// Fingerprint of the spec and inputs the result was produced from
+    pub fingerprint: String,
+
+    /// Whether the verification passed
+    pub passed: bool,
+
+    /// Combined command output
+    pub output: String,
+
+    /// Why the verification failed beyond its exit code, if it did
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub failure: Option<String>,
+
+    /// When the result was produced
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl CachedVerification {
+    /// A result produced now
+    pub fn new(fingerprint: String, passed: bool, output: String, failure: Option<String>) -> Self {
+        Self {
+            fingerprint,
+            passed,
+            output,
+            failure,
+            recorded_at: Utc::now().trunc_subsecs(0),
+        }
+    }
+}
+
+/// Directory of cached verification results, one file per constraint
+#[derive(Debug, Clone)]
+pub struct VerificationCache {
+    dir: PathBuf,
+}
+
+impl VerificationCache {
+    /// Use the cache in the given directory (created on first write)
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The cached result for a constraint, if it was produced from `fingerprint`
+    pub fn get(&self, id: &str, fingerprint: &str) -> Option<CachedVerification> {
+        let data = std::fs::read(self.entry_path(id)).ok()?;
+        serde_json::from_slice::<CachedVerification>(&data)
+            .ok()
+            .filter(|cached| cached.fingerprint == fingerprint)
+    }
+
+    /// Record a constraint's result, replacing any earlier one
+    pub fn put(&self, id: &str, result: &CachedVerification) -> Result<(), ConstraintError> {
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir)?;
+            // Results depend on the local machine; keep them out of version control
+            std::fs::write(self.dir.join(".gitignore"), "*\n")?;
+        }
+
+        let path = self.entry_path(id);
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(result)?)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Path of a constraint's cache entry
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+}
+
+/// Fingerprint a verification, or `None` when it declares no inputs
+///
+/// The SHA-256 covers the spec itself and, in path order, the relative path
+/// and content hash of every file its input globs match under `project_dir`.
+pub fn fingerprint(
+    spec: &VerificationSpec,
+    project_dir: &Path,
+) -> Result<Option<String>, ConstraintError> {
+    if spec.inputs.is_empty() {
+        return Ok(None);
+    }
+
+    // Only the inputs are patterns; the project path is matched literally
+    let root = PathBuf::from(glob::Pattern::escape(&project_dir.to_string_lossy()));
+
+    let mut files = vec![];
+    for input in &spec.inputs {
+        let pattern = root.join(input);
+        let matches = glob::glob(&pattern.to_string_lossy()).map_err(|e| {
+            ConstraintError::Validation(format!("Invalid input glob '{}': {}", input, e))
+        })?;
+        for entry in matches {
+            let path = entry.map_err(|e| ConstraintError::Io(e.into_error()))?;
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(spec)?);
+    for path in files {
+        let relative = path.strip_prefix(project_dir).unwrap_or(&path);
+        hasher.update(b"\0");
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(Sha256::digest(std::fs::read(&path)?));
+    }
+
+    Ok(Some(hex::encode(hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fingerprint_tracks_inputs_and_spec() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        std::fs::create_dir_all(project.join("src/nested")).unwrap();
+        std::fs::write(project.join("src/lib.rs"), "fn a() {}").unwrap();
+        std::fs::write(project.join("src/nested/mod.rs"), "fn b() {}").unwrap();
+        std::fs::write(project.join("README.md"), "docs").unwrap();
+
+        let spec = VerificationSpec {
+            inputs: vec!["src/**/*.rs".to_string()],
+            ..VerificationSpec::shell("cargo test")
+        };
+        let first = fingerprint(&spec, project).unwrap().unwrap();
+        assert_eq!(fingerprint(&spec, project).unwrap().unwrap(), first);
+
+        // Files outside the globs do not matter
+        std::fs::write(project.join("README.md"), "more docs").unwrap();
+        assert_eq!(fingerprint(&spec, project).unwrap().unwrap(), first);
+
+        // Changed, added or removed inputs do
+        std::fs::write(project.join("src/nested/mod.rs"), "fn c() {}").unwrap();
+        let changed = fingerprint(&spec, project).unwrap().unwrap();
+        assert_ne!(changed, first);
+        std::fs::write(project.join("src/new.rs"), "").unwrap();
+        assert_ne!(fingerprint(&spec, project).unwrap().unwrap(), changed);
+
+        // So do the command and environment
+        let mut with_env = spec.clone();
+        with_env
+            .env
+            .insert("RUSTFLAGS".to_string(), "-Dwarnings".to_string());
+        assert_ne!(
+            fingerprint(&with_env, project).unwrap(),
+            fingerprint(&spec, project).unwrap()
+        );
+
+        // Without inputs there is nothing to key a cache on
+        assert_eq!(
+            fingerprint(&VerificationSpec::shell("cargo test"), project).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_in_project_dir_with_glob_characters() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("project[1]*?")
+            .tempdir()
+            .unwrap();
+        let project = temp_dir.path();
+        std::fs::create_dir(project.join("src")).unwrap();
+        std::fs::write(project.join("src/lib.rs"), "fn a() {}").unwrap();
+
+        let spec = VerificationSpec {
+            inputs: vec!["src/*.rs".to_string()],
+            ..VerificationSpec::shell("cargo test")
+        };
+        let first = fingerprint(&spec, project).unwrap().unwrap();
+
+        // The input is still found, so changing it changes the fingerprint
+        std::fs::write(project.join("src/lib.rs"), "fn b() {}").unwrap();
+        assert_ne!(fingerprint(&spec, project).unwrap().unwrap(), first);
+    }
+
+    #[test]
+    fn test_cache_returns_matching_fingerprint_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = VerificationCache::new(temp_dir.path().join("cache"));
+        assert!(cache.get("nt-a1b2c3", "abc").is_none());
+
+        let result = CachedVerification::new("abc".to_string(), true, "ok".to_string(), None);
+        cache.put("nt-a1b2c3", &result).unwrap();
+        assert_eq!(cache.get("nt-a1b2c3", "abc"), Some(result));
+        assert!(cache.get("nt-a1b2c3", "def").is_none());
+        assert!(temp_dir.path().join("cache/.gitignore").exists());
+    }
+}
@@ -0,0 +1,114 @@
+//! Persistent ID index for the JSONL storage backend
+//!
+//! Maps each constraint ID to the category and file holding it, so lookups by
+//! ID do not have to scan every category on disk.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::core::error::ConstraintError;
+use crate::utils::config::JsonlLayout;
+
+/// Location of a single constraint on disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Category the constraint is filed under
+    pub category: String,
+
+    /// File holding the record, relative to the constraints directory
+    pub path: String,
+}
+
+/// ID → location index stored as JSON under `.newton/`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IdIndex {
+    /// Layout the paths were recorded for; a mismatch makes the index stale
+    pub layout: JsonlLayout,
+
+    /// Entries keyed by constraint ID
+    pub entries: BTreeMap<String, IndexEntry>,
+
+    /// Modification time of each category when the index was saved
+    #[serde(default)]
+    pub modified: BTreeMap<String, SystemTime>,
+}
+
+impl IdIndex {
+    /// Create an empty index for a layout
+    pub fn new(layout: JsonlLayout) -> Self {
+        Self {
+            layout,
+            entries: BTreeMap::new(),
+            modified: BTreeMap::new(),
+        }
+    }
+
+    /// Load an index, returning `None` when it is missing or unreadable
+    pub fn load(path: &Path) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    /// Save the index atomically
+    ///
+    /// Callers hold the index lock, as the temporary file name is shared.
+    pub fn save(&self, path: &Path) -> Result<(), ConstraintError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(self)?)?;
+        std::fs::rename(&temp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Look up the location of a constraint
+    pub fn get(&self, id: &str) -> Option<&IndexEntry> {
+        self.entries.get(id)
+    }
+
+    /// Record the location of a constraint
+    pub fn insert(&mut self, id: &str, entry: IndexEntry) {
+        self.entries.insert(id.to_string(), entry);
+    }
+
+    /// Forget a constraint
+    pub fn remove(&mut self, id: &str) {
+        self.entries.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("index.json");
+        assert!(IdIndex::load(&path).is_none());
+
+        let mut index = IdIndex::new(JsonlLayout::PerConstraint);
+        index.insert(
+            "nt-test01",
+            IndexEntry {
+                category: "security".to_string(),
+                path: "security/nt-test01.jsonl".to_string(),
+            },
+        );
+        index.save(&path).unwrap();
+
+        let loaded = IdIndex::load(&path).unwrap();
+        assert_eq!(loaded.layout, JsonlLayout::PerConstraint);
+        assert_eq!(loaded.get("nt-test01").unwrap().category, "security");
+
+        // A corrupt index is treated as missing
+        std::fs::write(&path, "not json").unwrap();
+        assert!(IdIndex::load(&path).is_none());
+    }
+}
@@ -1,17 +1,35 @@
 //! JSONL-based storage implementation for constraints
+//!
+//! Two layouts are supported: one file per constraint (`<category>/<id>.jsonl`)
+//! and one multi-record file per category (`<category>.jsonl`, one constraint per line).
+//! Revision history is appended to one file per constraint (`history/<id>.jsonl`)
+//! and alias records for retired IDs are kept in a single JSON file.
 
 use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use crate::core::alias::Alias;
 use crate::core::constraint::Constraint;
 use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
 use crate::core::loader::LoaderRegistry;
+use crate::storage::index::{IdIndex, IndexEntry};
+use crate::storage::{follow_alias, ConstraintStore};
+use crate::utils::config::JsonlLayout;
 use crate::utils::id::IdGenerator;
 
 /// JSONL-based storage for constraints
 pub struct JsonlStorage {
     constraints_dir: PathBuf,
     loader_registry: LoaderRegistry,
+    layout: JsonlLayout,
+    index_path: Option<PathBuf>,
+    history_dir: Option<PathBuf>,
+    aliases_path: Option<PathBuf>,
 }
 
 impl JsonlStorage {
@@ -20,86 +38,648 @@ impl JsonlStorage {
         Self {
             constraints_dir,
             loader_registry: LoaderRegistry::new(),
+            layout: JsonlLayout::default(),
+            index_path: None,
+            history_dir: None,
+            aliases_path: None,
         }
     }
 
-    /// Write a constraint to storage
-    pub fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
-        let file_path = self.constraint_file_path(&constraint.category, &constraint.id)?;
-
-        // Ensure directory exists
-        if let Some(parent) = file_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        // Create a copy with current version for writing
-        let mut constraint_to_write = constraint.clone();
-        constraint_to_write.version = self.loader_registry.current_version();
+    /// Use the given on-disk layout
+    pub fn with_layout(mut self, layout: JsonlLayout) -> Self {
+        self.layout = layout;
+        self
+    }
 
-        // Prepare data for writing
-        let data = serde_json::to_vec(&constraint_to_write)?;
+    /// Maintain a persistent ID index at the given path
+    pub fn with_index(mut self, index_path: PathBuf) -> Self {
+        self.index_path = Some(index_path);
+        self
+    }
 
-        // Atomic write with file locking
-        self.atomic_write(&file_path, &data)?;
+    /// Record revision history under the given directory
+    pub fn with_history(mut self, history_dir: PathBuf) -> Self {
+        self.history_dir = Some(history_dir);
+        self
+    }
 
-        Ok(())
+    /// Keep alias records for retired IDs at the given path
+    pub fn with_aliases(mut self, aliases_path: PathBuf) -> Self {
+        self.aliases_path = Some(aliases_path);
+        self
     }
 
     /// Read a constraint from storage
     pub fn read_constraint(&self, category: &str, id: &str) -> Result<Constraint, ConstraintError> {
+        if self.layout == JsonlLayout::PerCategory {
+            self.check_id(id)?;
+            return self
+                .read_category_file(category)?
+                .into_iter()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ConstraintError::NotFound { id: id.to_string() });
+        }
+
         let file_path = self.constraint_file_path(category, id)?;
 
         if !file_path.exists() {
             return Err(ConstraintError::NotFound { id: id.to_string() });
         }
 
-        let data = std::fs::read(&file_path)?;
-        let constraint: Constraint = serde_json::from_slice(&data)?;
+        let constraint = self.read_constraint_file(&file_path)?;
 
         // Validate ID format
-        if !IdGenerator::validate(&constraint.id) {
+        if !IdGenerator::is_well_formed(&constraint.id) {
             return Err(ConstraintError::InvalidIdFormat(constraint.id));
         }
 
         Ok(constraint)
     }
 
-    /// Read a constraint by ID (searches all categories)
-    pub fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
-        // Validate ID format first
-        if !IdGenerator::validate(id) {
+    /// Validate an ID's format
+    fn check_id(&self, id: &str) -> Result<(), ConstraintError> {
+        if !IdGenerator::is_well_formed(id) {
             return Err(ConstraintError::InvalidIdFormat(id.to_string()));
         }
+        Ok(())
+    }
+
+    /// Get the file path for a constraint
+    fn constraint_file_path(&self, category: &str, id: &str) -> Result<PathBuf, ConstraintError> {
+        // Validate ID format
+        self.check_id(id)?;
+
+        Ok(self
+            .constraints_dir
+            .join(category)
+            .join(format!("{}.jsonl", id)))
+    }
+
+    /// Get the multi-record file path for a category
+    fn category_file_path(&self, category: &str) -> PathBuf {
+        self.constraints_dir.join(format!("{}.jsonl", category))
+    }
+
+    /// List the categories present on disk for the active layout
+    fn category_names(&self) -> Result<Vec<String>, ConstraintError> {
+        let mut categories = vec![];
 
         if !self.constraints_dir.exists() {
+            return Ok(categories);
+        }
+
+        for entry in std::fs::read_dir(&self.constraints_dir)? {
+            let path = entry?.path();
+
+            let name = match self.layout {
+                JsonlLayout::PerConstraint if path.is_dir() => path.file_name(),
+                JsonlLayout::PerCategory
+                    if path.is_file()
+                        && path.extension().and_then(|s| s.to_str()) == Some("jsonl") =>
+                {
+                    path.file_stem()
+                }
+                _ => None,
+            };
+
+            if let Some(name) = name.and_then(|n| n.to_str()) {
+                categories.push(name.to_string());
+            }
+        }
+
+        categories.sort();
+        Ok(categories)
+    }
+
+    /// Get the file holding a constraint, relative to the constraints directory
+    fn relative_path(&self, category: &str, id: &str) -> String {
+        match self.layout {
+            JsonlLayout::PerConstraint => format!("{}/{}.jsonl", category, id),
+            JsonlLayout::PerCategory => format!("{}.jsonl", category),
+        }
+    }
+
+    /// Build an ID index by scanning the constraints directory
+    fn scan_index(&self) -> Result<IdIndex, ConstraintError> {
+        let mut index = IdIndex::new(self.layout);
+
+        for category in self.category_names()? {
+            let ids: Vec<String> = match self.layout {
+                JsonlLayout::PerConstraint => {
+                    std::fs::read_dir(self.constraints_dir.join(&category))?
+                        .filter_map(|entry| entry.ok().map(|e| e.path()))
+                        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                        .filter_map(|path| {
+                            path.file_stem()
+                                .and_then(|n| n.to_str())
+                                .map(str::to_string)
+                        })
+                        .collect()
+                }
+                JsonlLayout::PerCategory => {
+                    std::fs::read_to_string(self.category_file_path(&category))?
+                        .lines()
+                        .filter_map(line_id)
+                        .collect()
+                }
+            };
+
+            for id in ids {
+                let path = self.relative_path(&category, &id);
+                index.insert(
+                    &id,
+                    IndexEntry {
+                        category: category.clone(),
+                        path,
+                    },
+                );
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Modification time of each category's directory or file
+    ///
+    /// Adding, removing or moving a record changes the time of the category
+    /// holding it, so comparing these against the ones saved with the index
+    /// detects edits made outside this tool without reading any records.
+    fn category_stamps(&self) -> Result<BTreeMap<String, SystemTime>, ConstraintError> {
+        let mut stamps = BTreeMap::new();
+
+        for category in self.category_names()? {
+            let path = match self.layout {
+                JsonlLayout::PerConstraint => self.constraints_dir.join(&category),
+                JsonlLayout::PerCategory => self.category_file_path(&category),
+            };
+            stamps.insert(category, std::fs::metadata(&path)?.modified()?);
+        }
+
+        Ok(stamps)
+    }
+
+    /// Whether an index still describes the files on disk
+    ///
+    /// Categories in `touched` are about to be updated by this store and are
+    /// not compared.
+    fn index_matches(
+        &self,
+        index: &IdIndex,
+        stamps: &BTreeMap<String, SystemTime>,
+        touched: &[&str],
+    ) -> bool {
+        let untouched = |stamps: &BTreeMap<String, SystemTime>| {
+            stamps
+                .iter()
+                .filter(|(category, _)| !touched.contains(&category.as_str()))
+                .map(|(category, time)| (category.clone(), *time))
+                .collect::<Vec<_>>()
+        };
+
+        index.layout == self.layout && untouched(&index.modified) == untouched(stamps)
+    }
+
+    /// Take the exclusive lock guarding the index file
+    fn lock_index(&self, index_path: &Path) -> Result<std::fs::File, ConstraintError> {
+        if let Some(parent) = index_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(index_path.with_extension("lock"))?;
+        lock.lock_exclusive()?;
+        Ok(lock)
+    }
+
+    /// Rebuild the ID index from disk and save it under the lock
+    fn rebuild_index(&self, index_path: &Path) -> Result<IdIndex, ConstraintError> {
+        let lock = self.lock_index(index_path)?;
+
+        // Taken before the scan, so edits made during it show up as stale next time
+        let stamps = self.category_stamps()?;
+        let mut index = self.scan_index()?;
+        index.modified = stamps;
+        index.save(index_path)?;
+
+        // Lock is released when the file is dropped
+        drop(lock);
+        Ok(index)
+    }
+
+    /// Load the ID index, rebuilding it when it is missing, was built for
+    /// another layout or no longer matches the files on disk
+    fn load_index(&self, index_path: &Path) -> Result<IdIndex, ConstraintError> {
+        match IdIndex::load(index_path) {
+            Some(index) if self.index_matches(&index, &self.category_stamps()?, &[]) => Ok(index),
+            _ => self.rebuild_index(index_path),
+        }
+    }
+
+    /// Apply an edit to the ID index under an exclusive lock
+    ///
+    /// `touched` names the categories this store just changed; changes
+    /// anywhere else since the index was saved make it rescan first.
+    fn update_index<F>(&self, touched: &[&str], edit: F) -> Result<(), ConstraintError>
+    where
+        F: FnOnce(&mut IdIndex),
+    {
+        let Some(index_path) = &self.index_path else {
+            return Ok(());
+        };
+
+        let lock = self.lock_index(index_path)?;
+
+        let mut index = match IdIndex::load(index_path) {
+            Some(index) if self.index_matches(&index, &self.category_stamps()?, touched) => index,
+            _ => self.scan_index()?,
+        };
+        edit(&mut index);
+        index.modified = self.category_stamps()?;
+        index.save(index_path)?;
+
+        // Lock is released when the file is dropped
+        drop(lock);
+        Ok(())
+    }
+
+    /// Record a freshly written constraint in the ID index
+    fn index_written(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        let entry = IndexEntry {
+            category: constraint.category.clone(),
+            path: self.relative_path(&constraint.category, &constraint.id),
+        };
+        self.update_index(&[&constraint.category], |index| {
+            index.insert(&constraint.id, entry)
+        })
+    }
+
+    /// Read a constraint from the location recorded in the index
+    fn read_indexed(&self, entry: &IndexEntry, id: &str) -> Result<Constraint, ConstraintError> {
+        let path = self.constraints_dir.join(&entry.path);
+
+        if !path.exists() {
             return Err(ConstraintError::NotFound { id: id.to_string() });
         }
 
-        // Search through all categories
-        for category_entry in std::fs::read_dir(&self.constraints_dir)? {
-            let category_entry = category_entry?;
-            let category_path = category_entry.path();
-
-            if category_path.is_dir() {
-                if let Some(category_name) = category_path.file_name().and_then(|n| n.to_str()) {
-                    // Try to read from this category
-                    match self.read_constraint(category_name, id) {
-                        Ok(constraint) => return Ok(constraint),
-                        Err(ConstraintError::NotFound { .. }) => continue, // Not in this category
-                        Err(e) => return Err(e),                           // Other error
-                    }
+        match self.layout {
+            JsonlLayout::PerConstraint => {
+                let constraint = self.read_constraint_file(&path)?;
+                if constraint.id != id {
+                    return Err(ConstraintError::NotFound { id: id.to_string() });
                 }
+                Ok(constraint)
+            }
+            JsonlLayout::PerCategory => self.read_constraint(&entry.category, id),
+        }
+    }
+
+    /// Look up a constraint through the ID index
+    ///
+    /// An ID missing from an up-to-date index does not exist. An entry whose
+    /// file no longer holds the record makes the index rescan once.
+    fn read_via_index(&self, index_path: &Path, id: &str) -> Result<Constraint, ConstraintError> {
+        let index = self.load_index(index_path)?;
+
+        let Some(entry) = index.get(id) else {
+            return Err(ConstraintError::NotFound { id: id.to_string() });
+        };
+        match self.read_indexed(entry, id) {
+            Err(ConstraintError::NotFound { .. }) => {}
+            result => return result,
+        }
+
+        // Edits within the file system's timestamp resolution can go unnoticed
+        let index = self.rebuild_index(index_path)?;
+        match index.get(id) {
+            Some(entry) => self.read_indexed(entry, id),
+            None => Err(ConstraintError::NotFound { id: id.to_string() }),
+        }
+    }
+
+    /// Read a constraint from a specific file path
+    fn read_constraint_file(&self, file_path: &Path) -> Result<Constraint, ConstraintError> {
+        let data = std::fs::read(file_path)?;
+        let constraint = self.loader_registry.load_constraint(&data)?;
+        Ok(constraint)
+    }
+
+    /// Read every record from a category's multi-record file
+    fn read_category_file(&self, category: &str) -> Result<Vec<Constraint>, ConstraintError> {
+        let path = self.category_file_path(category);
+
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let data = std::fs::read(&path)?;
+        let mut constraints = vec![];
+
+        for result in self.loader_registry.load_jsonl(&data) {
+            match result {
+                Ok(constraint) => constraints.push(constraint),
+                Err(e) => {
+                    // Log error but continue with other records
+                    eprintln!(
+                        "Warning: Failed to load constraint from {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(constraints)
+    }
+
+    /// Rewrite a category's multi-record file under an exclusive lock
+    ///
+    /// The closure edits the raw lines; records that fail to parse are kept
+    /// verbatim so a rewrite never drops data.
+    fn rewrite_category_file<F>(&self, category: &str, edit: F) -> Result<(), ConstraintError>
+    where
+        F: FnOnce(&mut Vec<String>) -> Result<(), ConstraintError>,
+    {
+        std::fs::create_dir_all(&self.constraints_dir)?;
+
+        let path = self.category_file_path(category);
+        let lock_path = self
+            .constraints_dir
+            .join(format!("{}.jsonl.lock", category));
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)?;
+        lock.lock_exclusive()?;
+
+        let mut lines: Vec<String> = if path.exists() {
+            std::fs::read_to_string(&path)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(str::to_string)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        edit(&mut lines)?;
+
+        if lines.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        } else {
+            let mut data = lines.join("\n");
+            data.push('\n');
+            self.atomic_write(&path, data.as_bytes())?;
+        }
+
+        // Lock is released when the file is dropped
+        drop(lock);
+        Ok(())
+    }
+
+    /// Read a constraint stored under exactly this ID (searches all categories)
+    fn read_current(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        // Validate ID format first
+        self.check_id(id)?;
+
+        if let Some(index_path) = &self.index_path {
+            return self.read_via_index(index_path, id);
+        }
+
+        // Search through all categories
+        for category in self.category_names()? {
+            match self.read_constraint(&category, id) {
+                Ok(constraint) => return Ok(constraint),
+                Err(ConstraintError::NotFound { .. }) => continue, // Not in this category
+                Err(e) => return Err(e),                           // Other error
             }
         }
 
         Err(ConstraintError::NotFound { id: id.to_string() })
     }
 
+    /// Remove a record from a category without touching the ID index
+    fn remove_record(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        if self.layout == JsonlLayout::PerCategory {
+            self.check_id(id)?;
+
+            return self.rewrite_category_file(category, |lines| {
+                let before = lines.len();
+                lines.retain(|line| line_id(line).as_deref() != Some(id));
+
+                if lines.len() == before {
+                    return Err(ConstraintError::NotFound { id: id.to_string() });
+                }
+                Ok(())
+            });
+        }
+
+        let file_path = self.constraint_file_path(category, id)?;
+
+        if !file_path.exists() {
+            return Err(ConstraintError::NotFound { id: id.to_string() });
+        }
+
+        std::fs::remove_file(&file_path)?;
+        Ok(())
+    }
+
+    /// Read every revision from a history file
+    fn read_history_file(&self, path: &Path) -> Result<Vec<Revision>, ConstraintError> {
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut revisions = vec![];
+        for (index, line) in std::fs::read_to_string(path)?.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str(line) {
+                Ok(revision) => revisions.push(revision),
+                Err(e) => {
+                    // Log error but continue with other revisions
+                    eprintln!(
+                        "Warning: Failed to load revision from {} line {}: {}",
+                        path.display(),
+                        index + 1,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(revisions)
+    }
+
+    /// Serialize a constraint at the current format version
+    fn serialize_current(&self, constraint: &Constraint) -> Result<String, ConstraintError> {
+        let mut constraint_to_write = constraint.clone();
+        constraint_to_write.version = self.loader_registry.current_version();
+        Ok(serde_json::to_string(&constraint_to_write)?)
+    }
+
+    /// Get the path of the marker for a relocation in progress
+    fn relocation_path(&self) -> PathBuf {
+        self.constraints_dir.join(".relocate.json")
+    }
+
+    /// Take the exclusive lock serializing relocations
+    fn lock_relocation(&self) -> Result<std::fs::File, ConstraintError> {
+        std::fs::create_dir_all(&self.constraints_dir)?;
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.constraints_dir.join(".relocate.lock"))?;
+        lock.lock_exclusive()?;
+        Ok(lock)
+    }
+
+    /// Carry out a recorded relocation and remove its marker
+    ///
+    /// Each step can be repeated, so an interrupted relocation is finished by
+    /// running all of them again.
+    fn finish_relocation(&self, pending: &PendingRelocation) -> Result<(), ConstraintError> {
+        let constraint = &pending.constraint;
+        self.write_constraint(constraint)?;
+
+        match self.remove_record(&pending.old_category, &constraint.id) {
+            Ok(()) | Err(ConstraintError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+        // The index already points at the new location
+        self.update_index(&[&pending.old_category], |_| {})?;
+
+        std::fs::remove_file(self.relocation_path())?;
+        Ok(())
+    }
+
+    /// Complete a relocation interrupted by a crash, if one is recorded
+    ///
+    /// Call this once when opening the store, before reading from it.
+    pub fn recover(&self) -> Result<(), ConstraintError> {
+        if !self.relocation_path().exists() {
+            return Ok(());
+        }
+
+        let lock = self.lock_relocation()?;
+        // Another process may have finished it while we waited for the lock
+        if let Ok(data) = std::fs::read(self.relocation_path()) {
+            let pending: PendingRelocation = serde_json::from_slice(&data)?;
+            eprintln!(
+                "Note: completing the interrupted move of {} to [{}]",
+                pending.constraint.id, pending.constraint.category
+            );
+            self.finish_relocation(&pending)?;
+        }
+
+        // Lock is released when the file is dropped
+        drop(lock);
+        Ok(())
+    }
+
+    /// Perform atomic write with file locking
+    fn atomic_write(&self, file_path: &Path, data: &[u8]) -> Result<(), ConstraintError> {
+        // Create temporary file
+        let temp_path = file_path.with_extension("tmp");
+
+        // Write to temporary file
+        std::fs::write(&temp_path, data)?;
+
+        // Lock and move atomically
+        let file = std::fs::File::open(&temp_path)?;
+        file.lock_exclusive()?;
+
+        // Atomic move
+        std::fs::rename(&temp_path, file_path)?;
+
+        // Unlock (automatically released when file is dropped)
+        drop(file);
+
+        Ok(())
+    }
+}
+
+/// A relocation between categories, recorded before it starts
+#[derive(Serialize, Deserialize)]
+struct PendingRelocation {
+    /// Category the record is moving out of
+    old_category: String,
+
+    /// The record as it is written to its new category
+    constraint: Constraint,
+}
+
+/// Extract the ID from a raw JSONL record without fully loading it
+fn line_id(line: &str) -> Option<String> {
+    #[derive(Deserialize)]
+    struct IdProbe {
+        id: String,
+    }
+
+    serde_json::from_str::<IdProbe>(line).ok().map(|p| p.id)
+}
+
+impl ConstraintStore for JsonlStorage {
+    /// Write a constraint to storage
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        if self.layout == JsonlLayout::PerCategory {
+            self.check_id(&constraint.id)?;
+            let record = self.serialize_current(constraint)?;
+
+            self.rewrite_category_file(&constraint.category, |lines| {
+                match lines
+                    .iter()
+                    .position(|line| line_id(line).as_deref() == Some(constraint.id.as_str()))
+                {
+                    Some(index) => lines[index] = record,
+                    None => lines.push(record),
+                }
+                Ok(())
+            })?;
+
+            return self.index_written(constraint);
+        }
+
+        let file_path = self.constraint_file_path(&constraint.category, &constraint.id)?;
+
+        // Ensure directory exists
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Prepare data for writing at the current version
+        let data = self.serialize_current(constraint)?;
+
+        // Atomic write with file locking
+        self.atomic_write(&file_path, data.as_bytes())?;
+
+        self.index_written(constraint)
+    }
+
+    /// Read a constraint by ID (searches all categories, following aliases)
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        match self.read_current(id) {
+            Err(ConstraintError::NotFound { .. }) => follow_alias(self, id),
+            result => result,
+        }
+    }
+
     /// Read all constraints from a category
-    pub fn read_category_constraints(
+    fn read_category_constraints(
         &self,
         category: &str,
     ) -> Result<Vec<Constraint>, ConstraintError> {
+        if self.layout == JsonlLayout::PerCategory {
+            return self.read_category_file(category);
+        }
+
         let category_dir = self.constraints_dir.join(category);
 
         if !category_dir.exists() {
@@ -131,107 +711,140 @@ impl JsonlStorage {
     }
 
     /// Read all constraints across all categories
-    pub fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
         let mut all_constraints = vec![];
 
-        if !self.constraints_dir.exists() {
-            return Ok(all_constraints);
-        }
-
-        for category_entry in std::fs::read_dir(&self.constraints_dir)? {
-            let category_entry = category_entry?;
-            let category_path = category_entry.path();
-
-            if category_path.is_dir() {
-                if let Some(category_name) = category_path.file_name().and_then(|n| n.to_str()) {
-                    let mut category_constraints = self.read_category_constraints(category_name)?;
-                    all_constraints.append(&mut category_constraints);
-                }
-            }
+        for category in self.category_names()? {
+            let mut category_constraints = self.read_category_constraints(&category)?;
+            all_constraints.append(&mut category_constraints);
         }
 
         Ok(all_constraints)
     }
 
-    /// Search constraints by text content
-    pub fn search_constraints(
+    /// Delete a constraint
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        self.remove_record(category, id)?;
+        self.update_index(&[category], |index| index.remove(id))
+    }
+
+    /// Move the constraint to its new category through a relocation marker
+    ///
+    /// The marker is written before either category is touched, so a move
+    /// interrupted half way is completed by [`JsonlStorage::recover`].
+    fn relocate_constraint(
         &self,
-        query: &str,
-        category_filter: Option<&str>,
-    ) -> Result<Vec<Constraint>, ConstraintError> {
-        let constraints = if let Some(category) = category_filter {
-            self.read_category_constraints(category)?
-        } else {
-            self.read_all_constraints()?
-        };
+        constraint: &Constraint,
+        old_category: &str,
+    ) -> Result<(), ConstraintError> {
+        if old_category == constraint.category {
+            return self.write_constraint(constraint);
+        }
 
-        let query_lower = query.to_lowercase();
-        let results: Vec<Constraint> = constraints
-            .into_iter()
-            .filter(|c| {
-                c.text.to_lowercase().contains(&query_lower)
-                    || c.tags
-                        .iter()
-                        .any(|tag| tag.to_lowercase().contains(&query_lower))
-                    || c.category.to_lowercase().contains(&query_lower)
-                    || c.references.to_lowercase().contains(&query_lower)
-            })
-            .collect();
+        let lock = self.lock_relocation()?;
+        let pending = PendingRelocation {
+            old_category: old_category.to_string(),
+            constraint: constraint.clone(),
+        };
+        self.atomic_write(&self.relocation_path(), &serde_json::to_vec(&pending)?)?;
+        self.finish_relocation(&pending)?;
 
-        Ok(results)
+        // Lock is released when the file is dropped
+        drop(lock);
+        Ok(())
     }
 
-    /// Delete a constraint
-    pub fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
-        let file_path = self.constraint_file_path(category, id)?;
+    /// Record an alias in the aliases file under an exclusive lock
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError> {
+        let Some(aliases_path) = &self.aliases_path else {
+            return Ok(());
+        };
 
-        if !file_path.exists() {
-            return Err(ConstraintError::NotFound { id: id.to_string() });
+        if let Some(parent) = aliases_path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
-
-        std::fs::remove_file(&file_path)?;
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(aliases_path.with_extension("lock"))?;
+        lock.lock_exclusive()?;
+
+        let mut aliases = self.read_aliases()?;
+        aliases.retain(|a| a.alias != alias.alias);
+        aliases.push(alias.clone());
+        aliases.sort_by(|a, b| a.alias.cmp(&b.alias));
+        self.atomic_write(aliases_path, &serde_json::to_vec_pretty(&aliases)?)?;
+
+        // Lock is released when the file is dropped
+        drop(lock);
         Ok(())
     }
 
-    /// Get the file path for a constraint
-    fn constraint_file_path(&self, category: &str, id: &str) -> Result<PathBuf, ConstraintError> {
-        // Validate ID format
-        if !IdGenerator::validate(id) {
-            return Err(ConstraintError::InvalidIdFormat(id.to_string()));
+    /// Read the aliases file
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError> {
+        match &self.aliases_path {
+            Some(aliases_path) if aliases_path.exists() => {
+                Ok(serde_json::from_slice(&std::fs::read(aliases_path)?)?)
+            }
+            _ => Ok(vec![]),
         }
-
-        Ok(self
-            .constraints_dir
-            .join(category)
-            .join(format!("{}.jsonl", id)))
     }
 
-    /// Read a constraint from a specific file path
-    fn read_constraint_file(&self, file_path: &Path) -> Result<Constraint, ConstraintError> {
-        let data = std::fs::read(file_path)?;
-        let constraint = self.loader_registry.load_constraint(&data)?;
-        Ok(constraint)
+    /// Append a revision to the constraint's history file
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError> {
+        let Some(history_dir) = &self.history_dir else {
+            return Ok(());
+        };
+        self.check_id(&revision.constraint_id)?;
+
+        std::fs::create_dir_all(history_dir)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(history_dir.join(format!("{}.jsonl", revision.constraint_id)))?;
+        file.lock_exclusive()?;
+
+        let mut line = serde_json::to_string(revision)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+
+        // Lock is released when the file is dropped
+        drop(file);
+        Ok(())
     }
 
-    /// Perform atomic write with file locking
-    fn atomic_write(&self, file_path: &Path, data: &[u8]) -> Result<(), ConstraintError> {
-        // Create temporary file
-        let temp_path = file_path.with_extension("tmp");
+    /// Read the history file of a constraint
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError> {
+        self.check_id(id)?;
 
-        // Write to temporary file
-        std::fs::write(&temp_path, data)?;
+        match &self.history_dir {
+            Some(history_dir) => self.read_history_file(&history_dir.join(format!("{}.jsonl", id))),
+            None => Ok(vec![]),
+        }
+    }
 
-        // Lock and move atomically
-        let file = std::fs::File::open(&temp_path)?;
-        file.lock_exclusive()?;
+    /// Read every history file
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError> {
+        let Some(history_dir) = &self.history_dir else {
+            return Ok(vec![]);
+        };
+        if !history_dir.exists() {
+            return Ok(vec![]);
+        }
 
-        // Atomic move
-        std::fs::rename(&temp_path, file_path)?;
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(history_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+            .collect();
+        paths.sort();
 
-        // Unlock (automatically released when file is dropped)
-        drop(file);
+        let mut revisions = vec![];
+        for path in paths {
+            revisions.append(&mut self.read_history_file(&path)?);
+        }
 
-        Ok(())
+        Ok(revisions)
     }
 }
 
@@ -257,6 +870,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -296,6 +913,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -309,6 +930,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -342,6 +967,10 @@ mod tests {
             priority: None,
             references: "".to_string(),
             verification: None,
+            depends_on: vec![],
+            supersedes: vec![],
+            conflicts_with: vec![],
+            lifecycle: Default::default(),
         })
         .unwrap();
 
@@ -358,4 +987,349 @@ mod tests {
         let result = storage.read_constraint("security", "nt-test01");
         assert!(matches!(result, Err(ConstraintError::NotFound { .. })));
     }
+
+    fn test_constraint(id: &str, category: &str, text: &str) -> Constraint {
+        Constraint::new(crate::core::constraint::ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: category.to_string(),
+            text: text.to_string(),
+            author: "author".to_string(),
+            id: Some(id.to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ids_keep_their_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = JsonlStorage::new(temp_dir.path().join("constraints"));
+
+        // Records written before and after the workspace prefix changed
+        for id in ["nt-aaaaaa", "acme-bbbbbb"] {
+            storage
+                .write_constraint(&test_constraint(id, "security", id))
+                .unwrap();
+            assert_eq!(storage.read_constraint_by_id(id).unwrap().id, id);
+        }
+        assert_eq!(storage.read_all_constraints().unwrap().len(), 2);
+        assert!(matches!(
+            storage.read_constraint("security", "Acme-bbbbbb"),
+            Err(ConstraintError::InvalidIdFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_per_category_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage =
+            JsonlStorage::new(temp_dir.path().to_path_buf()).with_layout(JsonlLayout::PerCategory);
+
+        storage
+            .write_constraint(&test_constraint("nt-test01", "security", "First"))
+            .unwrap();
+        storage
+            .write_constraint(&test_constraint("nt-test02", "security", "Second"))
+            .unwrap();
+        storage
+            .write_constraint(&test_constraint("nt-test03", "testing", "Third"))
+            .unwrap();
+
+        // One file per category, one record per line
+        let data = std::fs::read_to_string(temp_dir.path().join("security.jsonl")).unwrap();
+        assert_eq!(data.lines().count(), 2);
+
+        // Rewriting a record replaces its line in place
+        storage
+            .write_constraint(&test_constraint("nt-test01", "security", "First, revised"))
+            .unwrap();
+        let data = std::fs::read_to_string(temp_dir.path().join("security.jsonl")).unwrap();
+        assert_eq!(data.lines().count(), 2);
+        assert!(data.lines().next().unwrap().contains("First, revised"));
+
+        assert_eq!(storage.read_all_constraints().unwrap().len(), 3);
+        let read = storage.read_constraint_by_id("nt-test03").unwrap();
+        assert_eq!(read.text, "Third");
+
+        storage.delete_constraint("security", "nt-test02").unwrap();
+        assert!(matches!(
+            storage.delete_constraint("security", "nt-test02"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+        assert_eq!(
+            storage.read_category_constraints("security").unwrap().len(),
+            1
+        );
+
+        // Removing the last record removes the file
+        storage.delete_constraint("testing", "nt-test03").unwrap();
+        assert!(!temp_dir.path().join("testing.jsonl").exists());
+    }
+
+    #[test]
+    fn test_per_category_keeps_unreadable_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage =
+            JsonlStorage::new(temp_dir.path().to_path_buf()).with_layout(JsonlLayout::PerCategory);
+
+        storage
+            .write_constraint(&test_constraint("nt-test01", "security", "First"))
+            .unwrap();
+        let path = temp_dir.path().join("security.jsonl");
+        let mut data = std::fs::read_to_string(&path).unwrap();
+        data.push_str("{not valid json\n");
+        std::fs::write(&path, data).unwrap();
+
+        // The broken line is reported and skipped on read
+        let results = LoaderRegistry::new().load_jsonl(&std::fs::read(&path).unwrap());
+        assert!(matches!(
+            results[1],
+            Err(crate::core::error::LoaderError::Line { line: 2, .. })
+        ));
+        assert_eq!(
+            storage.read_category_constraints("security").unwrap().len(),
+            1
+        );
+
+        // ...but preserved when the file is rewritten
+        storage
+            .write_constraint(&test_constraint("nt-test02", "security", "Second"))
+            .unwrap();
+        let data = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(data.lines().count(), 3);
+        assert!(data.contains("{not valid json"));
+    }
+
+    #[test]
+    fn test_id_index_maintained() {
+        let temp_dir = TempDir::new().unwrap();
+        let constraints_dir = temp_dir.path().join("constraints");
+        let index_path = temp_dir.path().join("index.json");
+        let storage = JsonlStorage::new(constraints_dir.clone()).with_index(index_path.clone());
+
+        storage
+            .write_constraint(&test_constraint("nt-test01", "security", "First"))
+            .unwrap();
+        storage
+            .write_constraint(&test_constraint("nt-test02", "testing", "Second"))
+            .unwrap();
+
+        let index = IdIndex::load(&index_path).unwrap();
+        assert_eq!(index.get("nt-test01").unwrap().category, "security");
+        assert_eq!(
+            index.get("nt-test02").unwrap().path,
+            "testing/nt-test02.jsonl"
+        );
+
+        storage.delete_constraint("security", "nt-test01").unwrap();
+        let index = IdIndex::load(&index_path).unwrap();
+        assert!(index.get("nt-test01").is_none());
+        assert!(matches!(
+            storage.read_constraint_by_id("nt-test01"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_id_index_rebuilt_when_missing_or_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        let constraints_dir = temp_dir.path().join("constraints");
+        let index_path = temp_dir.path().join("index.json");
+
+        // Constraints written without an index
+        let plain = JsonlStorage::new(constraints_dir.clone());
+        plain
+            .write_constraint(&test_constraint("nt-test01", "security", "First"))
+            .unwrap();
+
+        // A missing index is rebuilt on lookup
+        let storage = JsonlStorage::new(constraints_dir.clone()).with_index(index_path.clone());
+        assert_eq!(
+            storage.read_constraint_by_id("nt-test01").unwrap().text,
+            "First"
+        );
+        assert!(IdIndex::load(&index_path).is_some());
+
+        // A file moved behind the index's back is found after a rebuild
+        std::fs::create_dir_all(constraints_dir.join("testing")).unwrap();
+        std::fs::rename(
+            constraints_dir.join("security/nt-test01.jsonl"),
+            constraints_dir.join("testing/nt-test01.jsonl"),
+        )
+        .unwrap();
+        assert_eq!(
+            storage.read_constraint_by_id("nt-test01").unwrap().text,
+            "First"
+        );
+        let index = IdIndex::load(&index_path).unwrap();
+        assert_eq!(index.get("nt-test01").unwrap().category, "testing");
+
+        // A miss in an up-to-date index is answered without rescanning
+        let saved = std::fs::metadata(&index_path).unwrap().modified().unwrap();
+        assert!(matches!(
+            storage.read_constraint_by_id("nt-zzzzzz"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+        assert_eq!(
+            std::fs::metadata(&index_path).unwrap().modified().unwrap(),
+            saved
+        );
+
+        // A record added behind the index's back is found
+        plain
+            .write_constraint(&test_constraint("nt-test03", "docs", "Third"))
+            .unwrap();
+        assert_eq!(
+            storage.read_constraint_by_id("nt-test03").unwrap().text,
+            "Third"
+        );
+
+        // An index built for another layout is replaced
+        let per_category = JsonlStorage::new(constraints_dir)
+            .with_layout(JsonlLayout::PerCategory)
+            .with_index(index_path.clone());
+        per_category
+            .write_constraint(&test_constraint("nt-test02", "security", "Second"))
+            .unwrap();
+        let index = IdIndex::load(&index_path).unwrap();
+        assert_eq!(index.layout, JsonlLayout::PerCategory);
+        assert_eq!(index.get("nt-test02").unwrap().path, "security.jsonl");
+        assert!(index.get("nt-test01").is_none());
+    }
+
+    #[test]
+    fn test_history_appended_per_constraint() {
+        use crate::core::history::RevisionAction;
+
+        let temp_dir = TempDir::new().unwrap();
+        let history_dir = temp_dir.path().join("history");
+        let storage = JsonlStorage::new(temp_dir.path().join("constraints"))
+            .with_history(history_dir.clone());
+
+        let original = test_constraint("nt-test01", "security", "First");
+        let mut updated = original.clone();
+        updated.text = "Second".to_string();
+
+        for revision in [
+            Revision::between(RevisionAction::Created, "author", None, Some(&original)),
+            Revision::between(
+                RevisionAction::Updated,
+                "reviewer",
+                Some(&original),
+                Some(&updated),
+            ),
+        ] {
+            storage.append_revision(&revision.unwrap()).unwrap();
+        }
+        storage
+            .append_revision(
+                &Revision::between(
+                    RevisionAction::Created,
+                    "author",
+                    None,
+                    Some(&test_constraint("nt-test02", "security", "Other")),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let history = storage.read_history("nt-test01").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].actor, "reviewer");
+        assert!(history_dir.join("nt-test01.jsonl").exists());
+        assert_eq!(storage.read_all_history().unwrap().len(), 3);
+        assert!(storage.read_history("nt-test03").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_relocate_between_categories() {
+        for layout in [JsonlLayout::PerConstraint, JsonlLayout::PerCategory] {
+            let temp_dir = TempDir::new().unwrap();
+            let index_path = temp_dir.path().join("index.json");
+            let storage = JsonlStorage::new(temp_dir.path().join("constraints"))
+                .with_layout(layout)
+                .with_index(index_path.clone());
+
+            let mut constraint = test_constraint("nt-test01", "testing", "Movable");
+            storage.write_constraint(&constraint).unwrap();
+            storage
+                .write_constraint(&test_constraint("nt-test02", "testing", "Stays"))
+                .unwrap();
+
+            constraint.category = "security".to_string();
+            storage.relocate_constraint(&constraint, "testing").unwrap();
+
+            assert_eq!(
+                storage.read_constraint_by_id("nt-test01").unwrap().category,
+                "security"
+            );
+            assert_eq!(
+                storage.read_category_constraints("testing").unwrap().len(),
+                1
+            );
+            assert_eq!(storage.read_all_constraints().unwrap().len(), 2);
+            assert_eq!(
+                IdIndex::load(&index_path)
+                    .unwrap()
+                    .get("nt-test01")
+                    .unwrap()
+                    .category,
+                "security"
+            );
+
+            // A move that crashed after writing the new record is finished on recovery
+            constraint.category = "docs".to_string();
+            let pending = PendingRelocation {
+                old_category: "security".to_string(),
+                constraint: constraint.clone(),
+            };
+            std::fs::write(
+                storage.relocation_path(),
+                serde_json::to_vec(&pending).unwrap(),
+            )
+            .unwrap();
+            storage.write_constraint(&constraint).unwrap();
+
+            storage.recover().unwrap();
+            assert!(!storage.relocation_path().exists());
+            assert!(storage
+                .read_category_constraints("security")
+                .unwrap()
+                .is_empty());
+            assert_eq!(storage.read_all_constraints().unwrap().len(), 2);
+            assert_eq!(
+                storage.read_constraint_by_id("nt-test01").unwrap().category,
+                "docs"
+            );
+        }
+    }
+
+    #[test]
+    fn test_aliases_resolve_transparently() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = JsonlStorage::new(temp_dir.path().join("constraints"))
+            .with_index(temp_dir.path().join("index.json"))
+            .with_aliases(temp_dir.path().join("aliases.json"));
+
+        storage
+            .write_constraint(&test_constraint("nt-test02", "security", "Current"))
+            .unwrap();
+        storage
+            .write_alias(&Alias::new("nt-test01", "nt-test02"))
+            .unwrap();
+        // Re-recording an alias replaces the earlier target
+        storage
+            .write_alias(&Alias::new("nt-test01", "nt-test02"))
+            .unwrap();
+
+        assert_eq!(storage.read_aliases().unwrap().len(), 1);
+        assert_eq!(
+            storage.read_constraint_by_id("nt-test01").unwrap().text,
+            "Current"
+        );
+        assert!(matches!(
+            storage.read_constraint_by_id("nt-test03"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
 }
@@ -0,0 +1,219 @@
+//! In-memory storage implementation for constraints
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::core::alias::Alias;
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
+use crate::storage::{follow_alias, ConstraintStore};
+
+/// In-memory storage for constraints, keyed by ID
+///
+/// Nothing is persisted; this backend is meant for tests and tooling that
+/// needs a throwaway catalog.
+#[derive(Default)]
+pub struct MemoryStore {
+    constraints: Mutex<BTreeMap<String, Constraint>>,
+    history: Mutex<Vec<Revision>>,
+    aliases: Mutex<BTreeMap<String, Alias>>,
+}
+
+impl MemoryStore {
+    /// Create an empty in-memory store
+    #[allow(unused)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lock the underlying map
+    fn constraints(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Constraint>> {
+        // A poisoned lock only means another thread panicked mid-operation;
+        // the map itself is always left in a consistent state.
+        self.constraints
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Lock the revision log
+    fn history(&self) -> std::sync::MutexGuard<'_, Vec<Revision>> {
+        self.history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Lock the alias records
+    fn aliases(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Alias>> {
+        self.aliases
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ConstraintStore for MemoryStore {
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        self.constraints()
+            .insert(constraint.id.clone(), constraint.clone());
+        Ok(())
+    }
+
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        let constraint = self.constraints().get(id).cloned();
+
+        match constraint {
+            Some(constraint) => Ok(constraint),
+            None => follow_alias(self, id),
+        }
+    }
+
+    fn read_category_constraints(
+        &self,
+        category: &str,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        Ok(self
+            .constraints()
+            .values()
+            .filter(|c| c.category == category)
+            .cloned()
+            .collect())
+    }
+
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
+        Ok(self.constraints().values().cloned().collect())
+    }
+
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        let mut constraints = self.constraints();
+
+        match constraints.get(id) {
+            Some(constraint) if constraint.category == category => {
+                constraints.remove(id);
+                Ok(())
+            }
+            _ => Err(ConstraintError::NotFound { id: id.to_string() }),
+        }
+    }
+
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError> {
+        self.history().push(revision.clone());
+        Ok(())
+    }
+
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError> {
+        Ok(self
+            .history()
+            .iter()
+            .filter(|r| r.constraint_id == id)
+            .cloned()
+            .collect())
+    }
+
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError> {
+        Ok(self.history().clone())
+    }
+
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError> {
+        self.aliases().insert(alias.alias.clone(), alias.clone());
+        Ok(())
+    }
+
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError> {
+        Ok(self.aliases().values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+
+    fn constraint(id: &str, category: &str, text: &str) -> Constraint {
+        Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: category.to_string(),
+            text: text.to_string(),
+            author: "author".to_string(),
+            id: Some(id.to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_read_delete() {
+        let store = MemoryStore::new();
+        store
+            .write_constraint(&constraint("nt-test01", "security", "Hash passwords"))
+            .unwrap();
+
+        let read = store.read_constraint_by_id("nt-test01").unwrap();
+        assert_eq!(read.text, "Hash passwords");
+
+        // Deleting from the wrong category does not remove the record
+        assert!(matches!(
+            store.delete_constraint("testing", "nt-test01"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+
+        store.delete_constraint("security", "nt-test01").unwrap();
+        assert!(matches!(
+            store.read_constraint_by_id("nt-test01"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_category_and_search() {
+        let store = MemoryStore::new();
+        store
+            .write_constraint(&constraint(
+                "nt-test01",
+                "security",
+                "Password must be hashed",
+            ))
+            .unwrap();
+        store
+            .write_constraint(&constraint(
+                "nt-test02",
+                "testing",
+                "Unit tests recommended",
+            ))
+            .unwrap();
+
+        assert_eq!(store.read_all_constraints().unwrap().len(), 2);
+        assert_eq!(
+            store.read_category_constraints("security").unwrap().len(),
+            1
+        );
+
+        let results = store.search_constraints("password", None).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "nt-test01");
+
+        let results = store
+            .search_constraints("password", Some("testing"))
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_aliases_resolve_transparently() {
+        let store = MemoryStore::new();
+        store
+            .write_constraint(&constraint("nt-test02", "security", "Hash passwords"))
+            .unwrap();
+        store
+            .write_alias(&Alias::new("nt-test01", "nt-test02"))
+            .unwrap();
+
+        assert_eq!(
+            store.read_constraint_by_id("nt-test01").unwrap().id,
+            "nt-test02"
+        );
+        assert!(matches!(
+            store.read_constraint_by_id("nt-test03"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+}
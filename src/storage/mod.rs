@@ -1,6 +1,521 @@
 //! Storage layer for constraint persistence
 
+pub mod audit;
+pub mod cache;
+pub mod index;
 pub mod jsonl;
+pub mod memory;
+pub mod runs;
+pub mod signed;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 
+#[allow(unused_imports)]
+pub use audit::*;
+#[allow(unused_imports)]
+pub use cache::*;
 #[allow(unused_imports)]
 pub use jsonl::*;
+#[allow(unused_imports)]
+pub use memory::*;
+#[allow(unused_imports)]
+pub use runs::*;
+#[allow(unused_imports)]
+pub use signed::*;
+#[cfg(feature = "sqlite")]
+#[allow(unused_imports)]
+pub use sqlite::*;
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::core::alias::{resolve_chain, Alias};
+use crate::core::constraint::{Constraint, ValidationStatus};
+use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
+use crate::core::relation::Relation;
+use crate::utils::config::{SigningPolicy, StorageBackend, StorageConfig};
+use crate::utils::keys::Keyring;
+use crate::utils::workspace::Workspace;
+
+/// Storage backend for constraints
+///
+/// Commands only talk to this trait, so backends can be swapped without
+/// touching command logic.
+pub trait ConstraintStore {
+    /// Write a constraint, replacing any existing record with the same ID
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError>;
+
+    /// Read a constraint by ID (searches all categories, following aliases)
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError>;
+
+    /// Read all constraints from a category
+    fn read_category_constraints(&self, category: &str)
+        -> Result<Vec<Constraint>, ConstraintError>;
+
+    /// Read all constraints across all categories
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError>;
+
+    /// Delete a constraint
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError>;
+
+    /// Write a constraint that was previously filed under `old_category`
+    ///
+    /// Backends keyed by ID alone replace the record in place; file-based
+    /// backends record the move first so an interrupted one can be completed.
+    fn relocate_constraint(
+        &self,
+        constraint: &Constraint,
+        _old_category: &str,
+    ) -> Result<(), ConstraintError> {
+        self.write_constraint(constraint)
+    }
+
+    /// Store the outcome of the latest validation run in a constraint's record
+    ///
+    /// The status is bookkeeping rather than content: signatures do not cover
+    /// it, so decorators pass it through without re-signing, and no revision
+    /// is recorded for it.
+    fn record_validation_status(
+        &self,
+        constraint: &Constraint,
+        status: ValidationStatus,
+    ) -> Result<(), ConstraintError> {
+        let mut updated = constraint.clone();
+        updated.validation_status = status;
+        self.write_constraint(&updated)
+    }
+
+    /// Append a revision to a constraint's history
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError>;
+
+    /// Read the recorded history of a constraint, oldest first
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError>;
+
+    /// Read every recorded revision across all constraints
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError>;
+
+    /// Record an alias, replacing any earlier record for the same retired ID
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError>;
+
+    /// Read every alias record
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError>;
+
+    /// Search constraints by text content
+    fn search_constraints(
+        &self,
+        query: &str,
+        category_filter: Option<&str>,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        let constraints = if let Some(category) = category_filter {
+            self.read_category_constraints(category)?
+        } else {
+            self.read_all_constraints()?
+        };
+
+        let query_lower = query.to_lowercase();
+        Ok(constraints
+            .into_iter()
+            .filter(|c| matches_query(c, &query_lower))
+            .collect())
+    }
+}
+
+/// Check whether a constraint matches a lowercased search query
+fn matches_query(constraint: &Constraint, query_lower: &str) -> bool {
+    constraint.text.to_lowercase().contains(query_lower)
+        || constraint
+            .tags
+            .iter()
+            .any(|tag| tag.to_lowercase().contains(query_lower))
+        || constraint.category.to_lowercase().contains(query_lower)
+        || constraint.references.to_lowercase().contains(query_lower)
+}
+
+/// Resolve an ID that matched no constraint through the store's aliases
+///
+/// Backends call this from `read_constraint_by_id` on a miss. A notice naming
+/// the current ID is printed to stderr so stale references can be updated.
+pub fn follow_alias<S>(store: &S, id: &str) -> Result<Constraint, ConstraintError>
+where
+    S: ConstraintStore + ?Sized,
+{
+    let Some(target) = resolve_chain(&store.read_aliases()?, id) else {
+        return Err(ConstraintError::NotFound { id: id.to_string() });
+    };
+
+    eprintln!("Note: {} has been renamed to {}", id, target);
+    store.read_constraint_by_id(&target)
+}
+
+/// Resolve a full ID, retired ID or unique ID prefix to a stored constraint
+///
+/// This is the lookup every command taking an ID goes through. Exact IDs and
+/// aliases are tried first; otherwise `input` is matched against the start of
+/// each ID both with and without its prefix, so `nt-a3f` and `a3f` both find
+/// `nt-a3f2k9`. Several matches give an [`ConstraintError::AmbiguousId`]
+/// listing them.
+pub fn resolve_constraint(
+    store: &dyn ConstraintStore,
+    input: &str,
+) -> Result<Constraint, ConstraintError> {
+    match store.read_constraint_by_id(input) {
+        Ok(constraint) => return Ok(constraint),
+        // Partial IDs fail the backend's format check before any lookup
+        Err(ConstraintError::NotFound { .. }) | Err(ConstraintError::InvalidIdFormat(_)) => {}
+        Err(e) => return Err(e),
+    }
+
+    let not_found = || ConstraintError::NotFound {
+        id: input.to_string(),
+    };
+    if input.is_empty() {
+        return Err(not_found());
+    }
+
+    let matches = |id: &str| {
+        id.starts_with(input)
+            || id
+                .split_once('-')
+                .is_some_and(|(_, suffix)| suffix.starts_with(input))
+    };
+
+    let mut found: BTreeMap<String, Constraint> = store
+        .read_all_constraints()?
+        .into_iter()
+        .filter(|c| matches(&c.id))
+        .map(|c| (c.id.clone(), c))
+        .collect();
+
+    let aliases = store.read_aliases()?;
+    for alias in aliases.iter().filter(|a| matches(&a.alias)) {
+        let Some(target) = resolve_chain(&aliases, &alias.alias) else {
+            continue;
+        };
+        match store.read_constraint_by_id(&target) {
+            Ok(constraint) => {
+                found.insert(constraint.id.clone(), constraint);
+            }
+            Err(ConstraintError::NotFound { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    if found.len() > 1 {
+        return Err(ConstraintError::AmbiguousId {
+            prefix: input.to_string(),
+            candidates: found.into_keys().collect(),
+        });
+    }
+
+    found.into_values().next().ok_or_else(not_found)
+}
+
+/// Resolve each relation target given on the command line to a full ID
+///
+/// Empty values are dropped so `--depends-on ''` clears a relation.
+pub fn resolve_relation_targets(
+    store: &dyn ConstraintStore,
+    inputs: &[String],
+) -> Result<Vec<String>, ConstraintError> {
+    let mut targets: Vec<String> = vec![];
+    for input in inputs.iter().filter(|i| !i.trim().is_empty()) {
+        let id = resolve_constraint(store, input.trim())?.id;
+        if !targets.contains(&id) {
+            targets.push(id);
+        }
+    }
+    Ok(targets)
+}
+
+/// Check that a constraint's relationships hold against the stored catalog
+///
+/// Every target must exist (directly or through an alias), and following
+/// `depends-on` edges must never lead back to the constraint itself.
+pub fn check_relations(
+    store: &dyn ConstraintStore,
+    constraint: &Constraint,
+) -> Result<(), ConstraintError> {
+    let aliases = store.read_aliases()?;
+    let mut dependencies = BTreeMap::new();
+    for stored in store.read_all_constraints()? {
+        dependencies.insert(stored.id.clone(), stored.depends_on);
+    }
+    // The constraint being checked may not be stored yet, or stored in an older form
+    dependencies.insert(constraint.id.clone(), constraint.depends_on.clone());
+
+    let current = |id: &str| resolve_chain(&aliases, id).unwrap_or_else(|| id.to_string());
+
+    for relation in constraint.relations() {
+        if !dependencies.contains_key(&current(&relation.target)) {
+            return Err(ConstraintError::Validation(format!(
+                "{} {} {}, which does not exist",
+                relation.source, relation.kind, relation.target
+            )));
+        }
+    }
+
+    // Depth-first walk over depends-on edges, keeping the path for the message
+    let mut stack = vec![vec![constraint.id.clone()]];
+    let mut visited = HashSet::new();
+    while let Some(path) = stack.pop() {
+        let last = path.last().cloned().unwrap_or_default();
+        for next in dependencies.get(&last).into_iter().flatten() {
+            let next = current(next);
+            let mut extended = path.clone();
+            extended.push(next.clone());
+
+            if next == constraint.id {
+                return Err(ConstraintError::Validation(format!(
+                    "Dependency cycle: {}",
+                    extended.join(" -> ")
+                )));
+            }
+            if visited.insert(next) {
+                stack.push(extended);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// List the relationships of live constraints that point at `id`
+///
+/// Edges recorded against a retired ID of the constraint are included;
+/// archived constraints are not enforced, so their edges are skipped.
+pub fn incoming_relations(
+    store: &dyn ConstraintStore,
+    id: &str,
+) -> Result<Vec<Relation>, ConstraintError> {
+    let aliases = store.read_aliases()?;
+    let mut incoming = vec![];
+
+    for constraint in store.read_all_constraints()? {
+        if constraint.is_archived() || constraint.id == id {
+            continue;
+        }
+        for relation in constraint.relations() {
+            let target = resolve_chain(&aliases, &relation.target)
+                .unwrap_or_else(|| relation.target.clone());
+            if target == id {
+                incoming.push(relation);
+            }
+        }
+    }
+
+    incoming.sort();
+    Ok(incoming)
+}
+
+/// Check whether an ID is held by a constraint or kept as an alias, across all categories
+///
+/// `aliases` is passed in so callers checking many IDs read them only once.
+pub fn id_in_use(
+    store: &dyn ConstraintStore,
+    aliases: &[Alias],
+    id: &str,
+) -> Result<bool, ConstraintError> {
+    if resolve_chain(aliases, id).is_some() {
+        return Ok(true);
+    }
+
+    match store.read_constraint_by_id(id) {
+        Ok(_) => Ok(true),
+        Err(ConstraintError::NotFound { .. }) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Open the storage backend configured for a workspace
+///
+/// Every revision and validation status written through the returned store is
+/// also recorded in the workspace audit log.
+pub fn open(workspace: &Workspace) -> Result<Box<dyn ConstraintStore>, ConstraintError> {
+    let mut store = open_backend(workspace, &workspace.config().storage)?;
+
+    // Records are signed on write whenever a local key exists, and checked on
+    // read unless the signing policy is off
+    let policy = workspace.config().signing.policy;
+    let keyring = Keyring::load(&workspace.keys_dir())?;
+    if policy != SigningPolicy::Off || keyring.signing_key().is_some() {
+        store = Box::new(SignedStore::new(store, keyring, policy));
+    }
+
+    Ok(Box::new(AuditedStore::new(
+        store,
+        AuditLog::new(workspace.audit_path()),
+        workspace.config().actor(None),
+    )))
+}
+
+/// Open a specific storage backend within a workspace
+pub fn open_backend(
+    workspace: &Workspace,
+    storage: &StorageConfig,
+) -> Result<Box<dyn ConstraintStore>, ConstraintError> {
+    match storage.backend {
+        StorageBackend::Jsonl => {
+            let store = JsonlStorage::new(workspace.constraints_dir())
+                .with_layout(storage.layout)
+                .with_index(workspace.index_path())
+                .with_history(workspace.history_dir())
+                .with_aliases(workspace.aliases_path());
+            store.recover()?;
+            Ok(Box::new(store))
+        }
+        #[cfg(feature = "sqlite")]
+        StorageBackend::Sqlite => Ok(Box::new(SqliteStore::open(&workspace.database_path())?)),
+        #[cfg(not(feature = "sqlite"))]
+        StorageBackend::Sqlite => Err(ConstraintError::Config(
+            "The sqlite storage backend requires building with `--features sqlite`".to_string(),
+        )),
+    }
+}
+
+/// Copy every constraint, its revision history and alias records from one store into a target store
+///
+/// The target must be empty unless `replace` is set, in which case its
+/// existing constraints are removed first. Revisions the target already holds
+/// are not duplicated. Returns the number of constraints copied.
+pub fn convert(
+    source: &dyn ConstraintStore,
+    target: &dyn ConstraintStore,
+    replace: bool,
+) -> Result<usize, ConstraintError> {
+    let existing = target.read_all_constraints()?;
+    if !existing.is_empty() {
+        if !replace {
+            return Err(ConstraintError::Validation(format!(
+                "Target storage already contains {} constraint(s) (use --force to replace them)",
+                existing.len()
+            )));
+        }
+
+        for constraint in &existing {
+            target.delete_constraint(&constraint.category, &constraint.id)?;
+        }
+    }
+
+    let constraints = source.read_all_constraints()?;
+    for constraint in &constraints {
+        target.write_constraint(constraint)?;
+    }
+
+    let recorded: HashSet<String> = target
+        .read_all_history()?
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<_, _>>()?;
+    for revision in source.read_all_history()? {
+        if !recorded.contains(&serde_json::to_string(&revision)?) {
+            target.append_revision(&revision)?;
+        }
+    }
+
+    let aliases = target.read_aliases()?;
+    for alias in source.read_aliases()? {
+        if !aliases.contains(&alias) {
+            target.write_alias(&alias)?;
+        }
+    }
+
+    Ok(constraints.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::core::history::RevisionAction;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_convert_between_stores() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = JsonlStorage::new(temp_dir.path().join("constraints"))
+            .with_history(temp_dir.path().join("history"));
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            tags: vec!["auth".to_string()],
+            priority: Some("P1".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        source.write_constraint(&constraint).unwrap();
+        source
+            .append_revision(
+                &Revision::between(RevisionAction::Created, "author", None, Some(&constraint))
+                    .unwrap(),
+            )
+            .unwrap();
+
+        let target = MemoryStore::new();
+        assert_eq!(convert(&source, &target, false).unwrap(), 1);
+
+        let copied = target.read_constraint_by_id(&constraint.id).unwrap();
+        assert_eq!(
+            serde_json::to_value(&copied).unwrap(),
+            serde_json::to_value(&constraint).unwrap()
+        );
+
+        // Converting into a populated store is refused unless replacing
+        assert!(matches!(
+            convert(&source, &target, false),
+            Err(ConstraintError::Validation(_))
+        ));
+        assert_eq!(convert(&source, &target, true).unwrap(), 1);
+
+        // History is carried over once, even across repeated conversions
+        assert_eq!(target.read_history(&constraint.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_constraint_by_prefix() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = JsonlStorage::new(temp_dir.path().join("constraints"))
+            .with_aliases(temp_dir.path().join("aliases.json"));
+        for (id, text) in [
+            ("nt-a3f2k9", "Hash passwords"),
+            ("nt-a3b001", "Rotate keys"),
+            ("nt-zz0001", "Log logins"),
+        ] {
+            let constraint = Constraint::new(ConstraintParams {
+                r#type: ConstraintType::Must,
+                category: "security".to_string(),
+                text: text.to_string(),
+                author: "author".to_string(),
+                id: Some(id.to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+            store.write_constraint(&constraint).unwrap();
+        }
+        store
+            .write_alias(&Alias::new("nt-q00001", "nt-zz0001"))
+            .unwrap();
+
+        for input in ["nt-a3f2k9", "nt-a3f", "a3f"] {
+            assert_eq!(resolve_constraint(&store, input).unwrap().id, "nt-a3f2k9");
+        }
+        // Retired IDs resolve by prefix too
+        assert_eq!(resolve_constraint(&store, "q00").unwrap().id, "nt-zz0001");
+
+        match resolve_constraint(&store, "a3") {
+            Err(ConstraintError::AmbiguousId { candidates, .. }) => {
+                assert_eq!(candidates, vec!["nt-a3b001", "nt-a3f2k9"]);
+            }
+            other => panic!("expected an ambiguous prefix, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve_constraint(&store, "b9"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+        assert!(matches!(
+            resolve_constraint(&store, ""),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+}
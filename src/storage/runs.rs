@@ -0,0 +1,418 @@
+//! Validation runs recorded in `.newton/runs`
+//!
+//! Every `validate` invocation is stored as one JSON file named after its run
+//! ID, the UTC time it started (such as `20261017T093000Z`, with a `-2`, `-3`
+//! ... suffix for runs started in the same second). Runs hold the outcome of
+//! each constraint they checked, so later runs can be compared against them.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::constraint::{ConstraintType, ValidationStatus};
+use crate::core::error::ConstraintError;
+
+/// Outcome of checking one constraint in a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunStatus {
+    /// The verification command passed
+    Passed,
+    /// The verification command failed or could not be run
+    Failed,
+    /// The verification command was killed at its time limit
+    TimedOut,
+    /// There was no verification command to run
+    Skipped,
+    /// The structural check passed
+    Valid,
+    /// The structural check failed
+    Invalid,
+}
+
+impl RunStatus {
+    /// Uppercase label shown in results
+    pub fn label(&self) -> &'static str {
+        match self {
+            RunStatus::Passed => "PASSED",
+            RunStatus::Failed => "FAILED",
+            RunStatus::TimedOut => "TIMED OUT",
+            RunStatus::Skipped => "SKIPPED",
+            RunStatus::Valid => "VALID",
+            RunStatus::Invalid => "INVALID",
+        }
+    }
+
+    /// Icon shown in front of results
+    pub fn icon(&self) -> &'static str {
+        match self {
+            RunStatus::Passed | RunStatus::Valid => "✅",
+            RunStatus::Failed | RunStatus::Invalid => "❌",
+            RunStatus::TimedOut => "⏱️",
+            RunStatus::Skipped => "⏭️",
+        }
+    }
+
+    /// Whether the constraint failed its structural or verification check
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            RunStatus::Failed | RunStatus::TimedOut | RunStatus::Invalid
+        )
+    }
+
+    /// The validation status to store in the constraint's record
+    ///
+    /// Skipped verifications say nothing about the constraint, so the stored
+    /// status is left as it was.
+    pub fn recorded_status(&self) -> Option<ValidationStatus> {
+        match self {
+            RunStatus::Passed | RunStatus::Valid => Some(ValidationStatus::Valid),
+            RunStatus::Failed | RunStatus::TimedOut | RunStatus::Invalid => {
+                Some(ValidationStatus::Invalid)
+            }
+            RunStatus::Skipped => None,
+        }
+    }
+}
+
+/// One constraint's outcome in a recorded run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunResult {
+    /// ID of the checked constraint
+    pub constraint_id: String,
+
+    /// Constraint type at the time of the run
+    pub r#type: ConstraintType,
+
+    /// Category at the time of the run
+    pub category: String,
+
+    /// Constraint text at the time of the run
+    pub text: String,
+
+    /// Outcome of the check
+    pub status: RunStatus,
+
+    /// Command output, or the structural check's message
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub output: Option<String>,
+
+    /// Why the check did not pass
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+
+    /// How long the check took
+    pub duration_ms: u64,
+
+    /// Whether the result was reused from the verification cache
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub cached: bool,
+}
+
+/// A recorded `validate` invocation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRun {
+    /// Run ID, derived from the start time (see the module docs)
+    pub id: String,
+
+    /// When the run started
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub started_at: DateTime<Utc>,
+
+    /// Git commit checked out in the project directory, if it is a repository
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub git_revision: Option<String>,
+
+    /// Whether verification commands were executed (otherwise only structure was checked)
+    pub execute: bool,
+
+    /// Whether the run succeeded overall
+    pub passed: bool,
+
+    /// Outcome of each checked constraint, in the order they were reported
+    pub results: Vec<RunResult>,
+}
+
+impl ValidationRun {
+    /// A run starting now, with no results yet
+    pub fn new(git_revision: Option<String>, execute: bool) -> Self {
+        let started_at = Utc::now().trunc_subsecs(0);
+        Self {
+            id: started_at.format("%Y%m%dT%H%M%SZ").to_string(),
+            started_at,
+            git_revision,
+            execute,
+            passed: true,
+            results: vec![],
+        }
+    }
+
+    /// The result for a constraint, if the run checked it
+    pub fn result(&self, constraint_id: &str) -> Option<&RunResult> {
+        self.results
+            .iter()
+            .find(|result| result.constraint_id == constraint_id)
+    }
+
+    /// Number of results with the given status
+    pub fn count(&self, status: RunStatus) -> usize {
+        self.results.iter().filter(|r| r.status == status).count()
+    }
+}
+
+/// A constraint whose status differs between two runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatusChange {
+    /// ID of the constraint
+    pub constraint_id: String,
+
+    /// Constraint text, as of the later run that checked it
+    pub text: String,
+
+    /// Status in the earlier run, or `None` if it was not checked
+    pub before: Option<RunStatus>,
+
+    /// Status in the later run, or `None` if it was not checked
+    pub after: Option<RunStatus>,
+}
+
+/// List the constraints whose status changed from `base` to `run`
+///
+/// Constraints checked by only one of the runs are included with the other
+/// side missing. Changes come in the later run's order, followed by
+/// constraints only the earlier run checked.
+pub fn compare_runs(base: &ValidationRun, run: &ValidationRun) -> Vec<StatusChange> {
+    let mut changes: Vec<StatusChange> = run
+        .results
+        .iter()
+        .filter_map(|result| {
+            let before = base.result(&result.constraint_id).map(|r| r.status);
+            (before != Some(result.status)).then(|| StatusChange {
+                constraint_id: result.constraint_id.clone(),
+                text: result.text.clone(),
+                before,
+                after: Some(result.status),
+            })
+        })
+        .collect();
+
+    changes.extend(
+        base.results
+            .iter()
+            .filter(|result| run.result(&result.constraint_id).is_none())
+            .map(|result| StatusChange {
+                constraint_id: result.constraint_id.clone(),
+                text: result.text.clone(),
+                before: Some(result.status),
+                after: None,
+            }),
+    );
+
+    changes
+}
+
+/// Directory of recorded validation runs, one file per run
+#[derive(Debug, Clone)]
+pub struct RunLog {
+    dir: PathBuf,
+}
+
+impl RunLog {
+    /// Use the runs in the given directory (created on first save)
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Record a run, adding a suffix to its ID if another run already took it
+    ///
+    /// `before_write` is called once the ID is claimed, before the run is
+    /// written; if it fails, the claim is released and nothing is saved.
+    pub fn save(
+        &self,
+        run: &mut ValidationRun,
+        before_write: impl FnOnce(&ValidationRun) -> Result<(), ConstraintError>,
+    ) -> Result<(), ConstraintError> {
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir)?;
+            // Runs describe one checkout; keep them out of version control
+            std::fs::write(self.dir.join(".gitignore"), "*\n")?;
+        }
+
+        let base = run.started_at.format("%Y%m%dT%H%M%SZ").to_string();
+        let mut attempt = 1;
+        loop {
+            run.id = match attempt {
+                1 => base.clone(),
+                n => format!("{}-{}", base, n),
+            };
+            let path = self.dir.join(format!("{}.json", run.id));
+            let file = std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path);
+            match file {
+                Ok(mut file) => {
+                    if let Err(e) = before_write(run) {
+                        drop(file);
+                        std::fs::remove_file(&path)?;
+                        return Err(e);
+                    }
+                    file.write_all(&serde_json::to_vec_pretty(run)?)?;
+                    return Ok(());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => attempt += 1,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Every recorded run, oldest first
+    pub fn list(&self) -> Result<Vec<ValidationRun>, ConstraintError> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut runs = vec![];
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            // Claimed but not yet written by a run that is still saving
+            let data = std::fs::read(&path)?;
+            if data.is_empty() {
+                continue;
+            }
+            runs.push(serde_json::from_slice::<ValidationRun>(&data)?);
+        }
+
+        // Suffixes only order runs started in the same second; `-10` follows `-9`
+        runs.sort_by(|a, b| {
+            (a.started_at, a.id.len(), &a.id).cmp(&(b.started_at, b.id.len(), &b.id))
+        });
+        Ok(runs)
+    }
+
+    /// Find a run by its ID or a unique prefix of it
+    pub fn find(&self, reference: &str) -> Result<ValidationRun, ConstraintError> {
+        let runs = self.list()?;
+        if let Some(run) = runs.iter().find(|run| run.id == reference) {
+            return Ok(run.clone());
+        }
+
+        let mut matches: Vec<ValidationRun> = runs
+            .into_iter()
+            .filter(|run| run.id.starts_with(reference))
+            .collect();
+        match matches.len() {
+            0 => Err(ConstraintError::Validation(format!(
+                "No recorded run matches '{}' (see `constraint report --list`)",
+                reference
+            ))),
+            1 => Ok(matches.remove(0)),
+            _ => Err(ConstraintError::AmbiguousId {
+                prefix: reference.to_string(),
+                candidates: matches.into_iter().map(|run| run.id).collect(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn result(id: &str, status: RunStatus) -> RunResult {
+        RunResult {
+            constraint_id: id.to_string(),
+            r#type: ConstraintType::Must,
+            category: "testing".to_string(),
+            text: format!("Constraint {}", id),
+            status,
+            output: None,
+            error: None,
+            duration_ms: 5,
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_save_list_and_find_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = RunLog::new(temp_dir.path().join("runs"));
+        assert!(log.list().unwrap().is_empty());
+
+        let mut first = ValidationRun::new(Some("abc123".to_string()), true);
+        first.results.push(result("nt-aaaaaa", RunStatus::Passed));
+        let mut second = first.clone();
+        log.save(&mut first, |_| Ok(())).unwrap();
+        log.save(&mut second, |_| Ok(())).unwrap();
+
+        // Runs started in the same second get distinct IDs
+        assert_ne!(first.id, second.id);
+        assert!(second.id.starts_with(&first.id));
+        assert_eq!(log.list().unwrap(), vec![first.clone(), second.clone()]);
+        assert!(temp_dir.path().join("runs/.gitignore").exists());
+
+        assert_eq!(log.find(&second.id).unwrap(), second);
+        assert_eq!(log.find(&first.id).unwrap(), first);
+        assert!(matches!(
+            log.find(&first.id[..8]),
+            Err(ConstraintError::AmbiguousId { .. })
+        ));
+        assert!(log.find("1999").is_err());
+
+        // A failing hook leaves nothing behind, not even the claimed ID
+        let mut third = first.clone();
+        let refused = log.save(&mut third, |_| {
+            Err(ConstraintError::Validation(
+                "audit log unavailable".to_string(),
+            ))
+        });
+        assert!(refused.is_err());
+        assert_eq!(log.list().unwrap().len(), 2);
+        assert!(!temp_dir
+            .path()
+            .join(format!("runs/{}.json", third.id))
+            .exists());
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let mut base = ValidationRun::new(None, true);
+        base.results = vec![
+            result("nt-aaaaaa", RunStatus::Passed),
+            result("nt-bbbbbb", RunStatus::Passed),
+            result("nt-cccccc", RunStatus::Failed),
+        ];
+        let mut run = base.clone();
+        run.results = vec![
+            result("nt-aaaaaa", RunStatus::Passed),
+            result("nt-bbbbbb", RunStatus::TimedOut),
+            result("nt-dddddd", RunStatus::Skipped),
+        ];
+
+        let changes = compare_runs(&base, &run);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.constraint_id.as_str(), c.before, c.after))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "nt-bbbbbb",
+                    Some(RunStatus::Passed),
+                    Some(RunStatus::TimedOut)
+                ),
+                ("nt-dddddd", None, Some(RunStatus::Skipped)),
+                ("nt-cccccc", Some(RunStatus::Failed), None),
+            ]
+        );
+        assert!(compare_runs(&run, &run).is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+//! Signature-checking decorator around another constraint store
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use crate::core::alias::Alias;
+use crate::core::constraint::{Constraint, ValidationStatus};
+use crate::core::error::ConstraintError;
+use crate::core::history::Revision;
+use crate::storage::ConstraintStore;
+use crate::utils::config::SigningPolicy;
+use crate::utils::keys::Keyring;
+
+/// Store that signs records on write and checks them on read
+///
+/// Every record written is re-signed with the local key, replacing any
+/// earlier signature; without a key the stale signature is dropped (and
+/// under [`SigningPolicy::Require`] the write is refused). Validation
+/// statuses are outside the signed content and pass through untouched.
+/// Records read back are checked against the trusted keys and handled
+/// according to the policy.
+pub struct SignedStore {
+    inner: Box<dyn ConstraintStore>,
+    keyring: Keyring,
+    policy: SigningPolicy,
+    warned: Mutex<HashSet<String>>,
+}
+
+impl SignedStore {
+    /// Wrap a store with the given keys and policy
+    pub fn new(inner: Box<dyn ConstraintStore>, keyring: Keyring, policy: SigningPolicy) -> Self {
+        Self {
+            inner,
+            keyring,
+            policy,
+            warned: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Replace a record's signature with one from the local key
+    fn seal(&self, constraint: &Constraint) -> Result<Constraint, ConstraintError> {
+        let mut sealed = constraint.clone();
+        sealed.signature = None;
+
+        if self.keyring.signing_key().is_some() {
+            sealed.signature = Some(self.keyring.sign(&sealed)?);
+        } else if self.policy == SigningPolicy::Require {
+            return Err(ConstraintError::Signature {
+                id: constraint.id.clone(),
+                reason: "the workspace requires signed records and no signing key was found \
+                         (run `constraint sign --generate-key`)"
+                    .to_string(),
+            });
+        }
+
+        Ok(sealed)
+    }
+
+    /// Apply the signing policy to a loaded record
+    fn check(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        let status = self.keyring.verify(constraint);
+        if status.is_valid() {
+            return Ok(());
+        }
+
+        match self.policy {
+            SigningPolicy::Off => Ok(()),
+            SigningPolicy::Warn => {
+                // Records are read several times per command; warn once each
+                let first = self
+                    .warned
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .insert(constraint.id.clone());
+                if first {
+                    eprintln!("Warning: {} is {}", constraint.id, status);
+                }
+                Ok(())
+            }
+            SigningPolicy::Require => Err(ConstraintError::Signature {
+                id: constraint.id.clone(),
+                reason: format!("record is {}", status),
+            }),
+        }
+    }
+
+    /// Check every record in a batch
+    fn check_all(&self, constraints: Vec<Constraint>) -> Result<Vec<Constraint>, ConstraintError> {
+        for constraint in &constraints {
+            self.check(constraint)?;
+        }
+        Ok(constraints)
+    }
+}
+
+impl ConstraintStore for SignedStore {
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        self.inner.write_constraint(&self.seal(constraint)?)
+    }
+
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        let constraint = self.inner.read_constraint_by_id(id)?;
+        self.check(&constraint)?;
+        Ok(constraint)
+    }
+
+    fn read_category_constraints(
+        &self,
+        category: &str,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        self.check_all(self.inner.read_category_constraints(category)?)
+    }
+
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
+        self.check_all(self.inner.read_all_constraints()?)
+    }
+
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        self.inner.delete_constraint(category, id)
+    }
+
+    fn relocate_constraint(
+        &self,
+        constraint: &Constraint,
+        old_category: &str,
+    ) -> Result<(), ConstraintError> {
+        self.inner
+            .relocate_constraint(&self.seal(constraint)?, old_category)
+    }
+
+    fn record_validation_status(
+        &self,
+        constraint: &Constraint,
+        status: ValidationStatus,
+    ) -> Result<(), ConstraintError> {
+        self.inner.record_validation_status(constraint, status)
+    }
+
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError> {
+        self.inner.append_revision(revision)
+    }
+
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError> {
+        self.inner.read_history(id)
+    }
+
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError> {
+        self.inner.read_all_history()
+    }
+
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError> {
+        self.inner.write_alias(alias)
+    }
+
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError> {
+        self.inner.read_aliases()
+    }
+
+    fn search_constraints(
+        &self,
+        query: &str,
+        category_filter: Option<&str>,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        self.check_all(self.inner.search_constraints(query, category_filter)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::storage::JsonlStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_signed_store_policies() {
+        let temp_dir = TempDir::new().unwrap();
+        let keys_dir = temp_dir.path().join("keys");
+        Keyring::generate(&keys_dir).unwrap();
+
+        // Two views of the same files: one raw, as a hand edit would see them
+        let constraints_dir = temp_dir.path().join("constraints");
+        let raw = JsonlStorage::new(constraints_dir.clone());
+        let open = |policy| {
+            SignedStore::new(
+                Box::new(JsonlStorage::new(constraints_dir.clone())),
+                Keyring::load(&keys_dir).unwrap(),
+                policy,
+            )
+        };
+
+        let constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // Writes through the decorator are signed and read back cleanly
+        let store = open(SigningPolicy::Require);
+        store.write_constraint(&constraint).unwrap();
+        assert!(raw
+            .read_constraint_by_id(&constraint.id)
+            .unwrap()
+            .signature
+            .is_some());
+        assert_eq!(store.read_all_constraints().unwrap().len(), 1);
+
+        // Recording a validation status needs no key and keeps the signature
+        let signed = raw.read_constraint_by_id(&constraint.id).unwrap();
+        SignedStore::new(
+            Box::new(JsonlStorage::new(constraints_dir.clone())),
+            Keyring::default(),
+            SigningPolicy::Require,
+        )
+        .record_validation_status(&signed, ValidationStatus::Invalid)
+        .unwrap();
+        let recorded = store.read_constraint_by_id(&constraint.id).unwrap();
+        assert_eq!(recorded.validation_status, ValidationStatus::Invalid);
+        assert_eq!(recorded.signature, signed.signature);
+
+        // A hand edit behind its back is refused under require, tolerated under warn
+        let mut edited = raw.read_constraint_by_id(&constraint.id).unwrap();
+        edited.text = "Store passwords".to_string();
+        raw.write_constraint(&edited).unwrap();
+        assert!(matches!(
+            store.read_constraint_by_id(&constraint.id),
+            Err(ConstraintError::Signature { .. })
+        ));
+        assert!(store.read_all_constraints().is_err());
+        assert!(open(SigningPolicy::Warn)
+            .read_constraint_by_id(&constraint.id)
+            .is_ok());
+
+        // Without a signing key, require refuses writes
+        let unkeyed = SignedStore::new(
+            Box::new(JsonlStorage::new(constraints_dir.clone())),
+            Keyring::default(),
+            SigningPolicy::Require,
+        );
+        assert!(unkeyed.write_constraint(&constraint).is_err());
+    }
+}
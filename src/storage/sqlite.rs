@@ -0,0 +1,541 @@
+//! SQLite-based storage implementation for constraints
+//!
+//! Constraints are kept in an indexed `constraints` table alongside a `tags`
+//! table, an append-only `history` table of revisions and an `aliases` table. The full JSON record
+//! is stored in the `data` column so conversions to and from the JSONL tree are
+//! lossless.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::core::alias::Alias;
+use crate::core::constraint::Constraint;
+use crate::core::error::{ConstraintError, StorageError};
+use crate::core::history::Revision;
+use crate::core::loader::LoaderRegistry;
+use crate::storage::{follow_alias, ConstraintStore};
+use crate::utils::id::IdGenerator;
+
+/// Schema for the constraint database
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS constraints (
+    id TEXT PRIMARY KEY,
+    category TEXT NOT NULL,
+    type TEXT NOT NULL,
+    text TEXT NOT NULL,
+    data TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_constraints_category ON constraints(category);
+
+CREATE TABLE IF NOT EXISTS tags (
+    constraint_id TEXT NOT NULL REFERENCES constraints(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (constraint_id, tag)
+);
+CREATE INDEX IF NOT EXISTS idx_tags_tag ON tags(tag);
+
+CREATE TABLE IF NOT EXISTS history (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    constraint_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    old_data TEXT,
+    new_data TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_history_constraint ON history(constraint_id);
+
+CREATE TABLE IF NOT EXISTS aliases (
+    alias TEXT PRIMARY KEY,
+    target TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+";
+
+/// Changed fields of a revision, as stored in `old_data`/`new_data`
+type FieldMap = BTreeMap<String, serde_json::Value>;
+
+/// SQLite-backed storage for constraints
+pub struct SqliteStore {
+    conn: Connection,
+    loader_registry: LoaderRegistry,
+}
+
+impl SqliteStore {
+    /// Open (or create) a constraint database at the given path
+    pub fn open(path: &Path) -> Result<Self, ConstraintError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path).map_err(StorageError::from)?;
+        Self::with_connection(conn)
+    }
+
+    /// Create a store backed by an in-memory database
+    #[allow(unused)]
+    pub fn open_in_memory() -> Result<Self, ConstraintError> {
+        let conn = Connection::open_in_memory().map_err(StorageError::from)?;
+        Self::with_connection(conn)
+    }
+
+    /// Initialize the schema on a fresh connection
+    fn with_connection(conn: Connection) -> Result<Self, ConstraintError> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")
+            .and_then(|_| conn.execute_batch(SCHEMA))
+            .map_err(StorageError::from)?;
+
+        Ok(Self {
+            conn,
+            loader_registry: LoaderRegistry::new(),
+        })
+    }
+
+    /// Load revisions from `(constraint_id, action, actor, recorded_at, old_data, new_data)` rows
+    fn query_history(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<Revision>, ConstraintError> {
+        type HistoryRow = (String, String, String, i64, Option<String>, Option<String>);
+
+        let mut stmt = self.conn.prepare(sql).map_err(StorageError::from)?;
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })
+            .map_err(StorageError::from)?;
+
+        let mut revisions = vec![];
+        for row in rows {
+            let (constraint_id, action, actor, recorded_at, old_data, new_data): HistoryRow =
+                row.map_err(StorageError::from)?;
+
+            let parse = |data: Option<String>| -> Result<FieldMap, ConstraintError> {
+                Ok(match data {
+                    Some(data) => serde_json::from_str(&data)?,
+                    None => Default::default(),
+                })
+            };
+            let old = parse(old_data)?;
+            let new = parse(new_data)?;
+
+            let action =
+                serde_json::from_value(serde_json::Value::from(action.as_str())).map_err(|_| {
+                    StorageError::Corruption(format!("Unknown history action '{}'", action))
+                })?;
+
+            revisions.push(Revision {
+                constraint_id,
+                action,
+                actor,
+                timestamp: chrono::DateTime::from_timestamp(recorded_at, 0).unwrap_or_default(),
+                old,
+                new,
+            });
+        }
+
+        Ok(revisions)
+    }
+
+    /// Validate an ID's format
+    fn check_id(&self, id: &str) -> Result<(), ConstraintError> {
+        if !IdGenerator::is_well_formed(id) {
+            return Err(ConstraintError::InvalidIdFormat(id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Load constraints from the `data` column of a query
+    fn query_constraints(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        let mut stmt = self.conn.prepare(sql).map_err(StorageError::from)?;
+        let rows = stmt
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(StorageError::from)?;
+
+        let mut constraints = vec![];
+        for row in rows {
+            let data = row.map_err(StorageError::from)?;
+            match self.loader_registry.load_constraint(data.as_bytes()) {
+                Ok(constraint) => constraints.push(constraint),
+                Err(e) => {
+                    // Log error but continue with other records
+                    eprintln!("Warning: Failed to load constraint from database: {}", e);
+                }
+            }
+        }
+
+        Ok(constraints)
+    }
+
+    /// Fetch the stored JSON for a constraint, if any
+    fn read_data(&self, id: &str) -> Result<Option<String>, ConstraintError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT data FROM constraints WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(StorageError::from)?)
+    }
+}
+
+impl ConstraintStore for SqliteStore {
+    fn write_constraint(&self, constraint: &Constraint) -> Result<(), ConstraintError> {
+        self.check_id(&constraint.id)?;
+
+        // Store the record at the current format version
+        let mut constraint_to_write = constraint.clone();
+        constraint_to_write.version = self.loader_registry.current_version();
+        let data = serde_json::to_string(&constraint_to_write)?;
+        let constraint_type = serde_json::to_value(constraint.r#type)?;
+
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(StorageError::from)?;
+
+        tx.execute(
+            "INSERT INTO constraints (id, category, type, text, data, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(id) DO UPDATE SET
+                category = excluded.category,
+                type = excluded.type,
+                text = excluded.text,
+                data = excluded.data,
+                updated_at = excluded.updated_at",
+            params![
+                constraint.id,
+                constraint.category,
+                constraint_type.as_str().unwrap_or_default(),
+                constraint.text,
+                data,
+                constraint.updated_at.timestamp(),
+            ],
+        )
+        .map_err(StorageError::from)?;
+
+        tx.execute(
+            "DELETE FROM tags WHERE constraint_id = ?1",
+            params![constraint.id],
+        )
+        .map_err(StorageError::from)?;
+        for tag in &constraint.tags {
+            tx.execute(
+                "INSERT OR IGNORE INTO tags (constraint_id, tag) VALUES (?1, ?2)",
+                params![constraint.id, tag],
+            )
+            .map_err(StorageError::from)?;
+        }
+
+        tx.commit().map_err(StorageError::from)?;
+        Ok(())
+    }
+
+    fn read_constraint_by_id(&self, id: &str) -> Result<Constraint, ConstraintError> {
+        self.check_id(id)?;
+
+        match self.read_data(id)? {
+            Some(data) => Ok(self.loader_registry.load_constraint(data.as_bytes())?),
+            None => follow_alias(self, id),
+        }
+    }
+
+    fn read_category_constraints(
+        &self,
+        category: &str,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        self.query_constraints(
+            "SELECT data FROM constraints WHERE category = ?1 ORDER BY id",
+            &[&category],
+        )
+    }
+
+    fn read_all_constraints(&self) -> Result<Vec<Constraint>, ConstraintError> {
+        self.query_constraints("SELECT data FROM constraints ORDER BY category, id", &[])
+    }
+
+    fn delete_constraint(&self, category: &str, id: &str) -> Result<(), ConstraintError> {
+        self.check_id(id)?;
+
+        let deleted = self
+            .conn
+            .execute(
+                "DELETE FROM constraints WHERE id = ?1 AND category = ?2",
+                params![id, category],
+            )
+            .map_err(StorageError::from)?;
+
+        if deleted == 0 {
+            return Err(ConstraintError::NotFound { id: id.to_string() });
+        }
+
+        Ok(())
+    }
+
+    fn append_revision(&self, revision: &Revision) -> Result<(), ConstraintError> {
+        self.check_id(&revision.constraint_id)?;
+
+        let encode = |fields: &FieldMap| {
+            if fields.is_empty() {
+                Ok(None)
+            } else {
+                serde_json::to_string(fields).map(Some)
+            }
+        };
+
+        self.conn
+            .execute(
+                "INSERT INTO history (constraint_id, action, actor, recorded_at, old_data, new_data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    revision.constraint_id,
+                    revision.action.to_string(),
+                    revision.actor,
+                    revision.timestamp.timestamp(),
+                    encode(&revision.old)?,
+                    encode(&revision.new)?,
+                ],
+            )
+            .map_err(StorageError::from)?;
+
+        Ok(())
+    }
+
+    fn read_history(&self, id: &str) -> Result<Vec<Revision>, ConstraintError> {
+        self.check_id(id)?;
+        self.query_history(
+            "SELECT constraint_id, action, actor, recorded_at, old_data, new_data
+             FROM history WHERE constraint_id = ?1 ORDER BY seq",
+            &[&id],
+        )
+    }
+
+    fn write_alias(&self, alias: &Alias) -> Result<(), ConstraintError> {
+        self.conn
+            .execute(
+                "INSERT INTO aliases (alias, target, created_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(alias) DO UPDATE SET
+                    target = excluded.target,
+                    created_at = excluded.created_at",
+                params![alias.alias, alias.target, alias.created_at.timestamp()],
+            )
+            .map_err(StorageError::from)?;
+
+        Ok(())
+    }
+
+    fn read_aliases(&self) -> Result<Vec<Alias>, ConstraintError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT alias, target, created_at FROM aliases ORDER BY alias")
+            .map_err(StorageError::from)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(Alias {
+                    alias: row.get(0)?,
+                    target: row.get(1)?,
+                    created_at: chrono::DateTime::from_timestamp(row.get(2)?, 0)
+                        .unwrap_or_default(),
+                })
+            })
+            .map_err(StorageError::from)?;
+
+        Ok(rows
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(StorageError::from)?)
+    }
+
+    fn read_all_history(&self) -> Result<Vec<Revision>, ConstraintError> {
+        self.query_history(
+            "SELECT constraint_id, action, actor, recorded_at, old_data, new_data
+             FROM history ORDER BY constraint_id, seq",
+            &[],
+        )
+    }
+
+    fn search_constraints(
+        &self,
+        query: &str,
+        category_filter: Option<&str>,
+    ) -> Result<Vec<Constraint>, ConstraintError> {
+        // Escape LIKE wildcards so the query is matched literally
+        let pattern = format!(
+            "%{}%",
+            query
+                .to_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        self.query_constraints(
+            "SELECT c.data FROM constraints c
+             WHERE (lower(c.text) LIKE ?1 ESCAPE '\\'
+                 OR lower(c.category) LIKE ?1 ESCAPE '\\'
+                 OR lower(coalesce(json_extract(c.data, '$.references'), '')) LIKE ?1 ESCAPE '\\'
+                 OR EXISTS (SELECT 1 FROM tags t
+                            WHERE t.constraint_id = c.id AND lower(t.tag) LIKE ?1 ESCAPE '\\'))
+               AND (?2 IS NULL OR c.category = ?2)
+             ORDER BY c.category, c.id",
+            &[&pattern, &category_filter],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use crate::core::history::RevisionAction;
+
+    fn constraint(id: &str, category: &str, text: &str, tags: &[&str]) -> Constraint {
+        Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: category.to_string(),
+            text: text.to_string(),
+            author: "author".to_string(),
+            id: Some(id.to_string()),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let original = constraint("nt-test01", "security", "Hash passwords", &["auth"]);
+        store.write_constraint(&original).unwrap();
+
+        let read = store.read_constraint_by_id("nt-test01").unwrap();
+        assert_eq!(
+            serde_json::to_value(&read).unwrap(),
+            serde_json::to_value(&original).unwrap()
+        );
+        assert_eq!(
+            store.read_category_constraints("security").unwrap().len(),
+            1
+        );
+        assert!(store
+            .read_category_constraints("testing")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_uses_tags_and_text() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store
+            .write_constraint(&constraint(
+                "nt-test01",
+                "security",
+                "Hash passwords",
+                &["auth"],
+            ))
+            .unwrap();
+        store
+            .write_constraint(&constraint("nt-test02", "testing", "Unit tests 100%", &[]))
+            .unwrap();
+
+        let results = store.search_constraints("AUTH", None).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "nt-test01");
+
+        // Wildcards in the query are matched literally
+        let results = store.search_constraints("100%", None).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(store.search_constraints("_", None).unwrap().is_empty());
+
+        let results = store
+            .search_constraints("passwords", Some("testing"))
+            .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        let original = constraint("nt-test01", "security", "Hash passwords", &[]);
+        let mut updated = original.clone();
+        updated.text = "Hash passwords with argon2id".to_string();
+
+        let revision = Revision::between(
+            RevisionAction::Updated,
+            "reviewer",
+            Some(&original),
+            Some(&updated),
+        )
+        .unwrap();
+        store.append_revision(&revision).unwrap();
+
+        // A revision in the same second still reads back after the first
+        let mut reverted = Revision::between(
+            RevisionAction::Updated,
+            "reviewer",
+            Some(&updated),
+            Some(&original),
+        )
+        .unwrap();
+        reverted.timestamp = revision.timestamp;
+        store.append_revision(&reverted).unwrap();
+
+        let history = store.read_history("nt-test01").unwrap();
+        assert_eq!(history, vec![revision, reverted]);
+        assert!(store.read_history("nt-test02").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_requires_matching_category() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store
+            .write_constraint(&constraint("nt-test01", "security", "Hash passwords", &[]))
+            .unwrap();
+
+        assert!(matches!(
+            store.delete_constraint("testing", "nt-test01"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+
+        store.delete_constraint("security", "nt-test01").unwrap();
+        assert!(matches!(
+            store.read_constraint_by_id("nt-test01"),
+            Err(ConstraintError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_aliases_resolve_transparently() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store
+            .write_constraint(&constraint("nt-test02", "security", "Hash passwords", &[]))
+            .unwrap();
+        store
+            .write_alias(&Alias::new("nt-test01", "nt-test02"))
+            .unwrap();
+        store
+            .write_alias(&Alias::new("nt-test01", "nt-test02"))
+            .unwrap();
+
+        assert_eq!(store.read_aliases().unwrap().len(), 1);
+        assert_eq!(
+            store.read_constraint_by_id("nt-test01").unwrap().id,
+            "nt-test02"
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Workspace configuration stored in `.newton/config.toml`
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::core::constraint::ConstraintType;
+use crate::core::error::ConstraintError;
+use crate::utils::id::IdGenerator;
+
+/// Name of the configuration file inside the `.newton` directory
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Workspace-wide settings shared by all commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// Author used when `--author` is not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_author: Option<String>,
+
+    /// Categories constraints may be filed under (empty allows any)
+    pub allowed_categories: Vec<String>,
+
+    /// Prefix for generated constraint IDs (the `nt` in `nt-a3f2k9`)
+    pub id_prefix: String,
+
+    /// Output format used when `--format` is not given (human, json)
+    pub default_format: String,
+
+    /// Storage backend settings
+    pub storage: StorageConfig,
+
+    /// Review sign-offs each constraint type needs
+    pub approvals: ApprovalConfig,
+
+    /// Record signing settings
+    pub signing: SigningConfig,
+
+    /// Verification command settings
+    pub verification: VerificationConfig,
+}
+
+/// Verification command settings (`[verification]` table)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerificationConfig {
+    /// Seconds a verification may run when neither its spec nor `--timeout` sets a limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// Record signature settings (`[signing]` table)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SigningConfig {
+    /// What to do when a loaded record is unsigned or fails verification
+    pub policy: SigningPolicy,
+}
+
+/// How strictly record signatures are enforced when loading constraints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningPolicy {
+    /// Signatures are not checked
+    #[default]
+    Off,
+    /// Unsigned or tampered records are loaded with a warning
+    Warn,
+    /// Unsigned or tampered records are refused, and writes need a signing key
+    Require,
+}
+
+impl std::fmt::Display for SigningPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SigningPolicy::Off => write!(f, "off"),
+            SigningPolicy::Warn => write!(f, "warn"),
+            SigningPolicy::Require => write!(f, "require"),
+        }
+    }
+}
+
+/// Approvals required per constraint type (`[approvals]` table)
+///
+/// A constraint whose current content has fewer approvals than its type
+/// requires is flagged by `validate`. Zero (the default) disables the check.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApprovalConfig {
+    pub must: u32,
+    pub shall: u32,
+    pub should: u32,
+    pub may: u32,
+    pub forbidden: u32,
+}
+
+impl ApprovalConfig {
+    /// Number of approvals a constraint of this type needs
+    pub fn required(&self, r#type: ConstraintType) -> u32 {
+        match r#type {
+            ConstraintType::Must => self.must,
+            ConstraintType::Shall => self.shall,
+            ConstraintType::Should => self.should,
+            ConstraintType::May => self.may,
+            ConstraintType::Forbidden => self.forbidden,
+        }
+    }
+}
+
+/// Storage backend selection (`[storage]` table)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Backend holding the constraint catalog
+    pub backend: StorageBackend,
+
+    /// File layout used by the jsonl backend
+    pub layout: JsonlLayout,
+}
+
+impl std::fmt::Display for StorageConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.backend {
+            StorageBackend::Jsonl => write!(f, "jsonl ({})", self.layout),
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// On-disk layouts for the jsonl backend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JsonlLayout {
+    /// One file per constraint: `<category>/<id>.jsonl`
+    #[default]
+    PerConstraint,
+    /// One file per category with one constraint per line: `<category>.jsonl`
+    PerCategory,
+}
+
+impl std::fmt::Display for JsonlLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonlLayout::PerConstraint => write!(f, "per-constraint"),
+            JsonlLayout::PerCategory => write!(f, "per-category"),
+        }
+    }
+}
+
+impl std::str::FromStr for JsonlLayout {
+    type Err = ConstraintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "per-constraint" => Ok(JsonlLayout::PerConstraint),
+            "per-category" => Ok(JsonlLayout::PerCategory),
+            _ => Err(ConstraintError::Config(format!(
+                "Unknown jsonl layout '{}': must be per-constraint or per-category",
+                s
+            ))),
+        }
+    }
+}
+
+/// Available storage backends
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// One JSON file per constraint under `.newton/constraints/<category>/`
+    #[default]
+    Jsonl,
+    /// Indexed tables in `.newton/constraints.db` (requires the `sqlite` feature)
+    Sqlite,
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageBackend::Jsonl => write!(f, "jsonl"),
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = ConstraintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jsonl" => Ok(StorageBackend::Jsonl),
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            _ => Err(ConstraintError::Config(format!(
+                "Unknown storage backend '{}': must be jsonl or sqlite",
+                s
+            ))),
+        }
+    }
+}
+
+impl Default for WorkspaceConfig {
+    fn default() -> Self {
+        Self {
+            default_author: None,
+            allowed_categories: vec![],
+            id_prefix: IdGenerator::DEFAULT_PREFIX.to_string(),
+            default_format: "human".to_string(),
+            storage: StorageConfig::default(),
+            approvals: ApprovalConfig::default(),
+            signing: SigningConfig::default(),
+            verification: VerificationConfig::default(),
+        }
+    }
+}
+
+impl WorkspaceConfig {
+    /// Load configuration from a file, falling back to defaults when it does not exist
+    pub fn load(path: &Path) -> Result<Self, ConstraintError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = std::fs::read_to_string(path)?;
+        let config: Self = toml::from_str(&data).map_err(|e| {
+            ConstraintError::Config(format!("Failed to parse {}: {}", path.display(), e))
+        })?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Write configuration to a file
+    pub fn save(&self, path: &Path) -> Result<(), ConstraintError> {
+        self.validate()?;
+
+        let data = toml::to_string_pretty(self)
+            .map_err(|e| ConstraintError::Config(format!("Failed to serialize config: {}", e)))?;
+        std::fs::write(path, data)?;
+
+        Ok(())
+    }
+
+    /// Validate configuration values
+    pub fn validate(&self) -> Result<(), ConstraintError> {
+        if !IdGenerator::is_valid_prefix(&self.id_prefix) {
+            return Err(ConstraintError::Config(format!(
+                "Invalid id_prefix '{}': must be 1-8 lowercase letters",
+                self.id_prefix
+            )));
+        }
+
+        if !["human", "json"].contains(&self.default_format.as_str()) {
+            return Err(ConstraintError::Config(format!(
+                "Invalid default_format '{}': must be human or json",
+                self.default_format
+            )));
+        }
+
+        if self.verification.timeout_secs == Some(0) {
+            return Err(ConstraintError::Config(
+                "verification.timeout_secs must be at least 1".to_string(),
+            ));
+        }
+
+        if let Some(author) = &self.default_author {
+            if author.trim().is_empty() {
+                return Err(ConstraintError::Config(
+                    "default_author cannot be empty".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a category is permitted in this workspace
+    pub fn is_category_allowed(&self, category: &str) -> bool {
+        self.allowed_categories.is_empty() || self.allowed_categories.iter().any(|c| c == category)
+    }
+
+    /// Reject categories the workspace does not permit
+    pub fn check_category(&self, category: &str) -> Result<(), ConstraintError> {
+        if !self.is_category_allowed(category) {
+            return Err(ConstraintError::Validation(format!(
+                "Category '{}' is not allowed in this workspace (allowed: {})",
+                category,
+                self.allowed_categories.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Resolve the output format, preferring an explicit command-line value
+    pub fn output_format(&self, explicit: Option<&str>) -> String {
+        explicit.unwrap_or(&self.default_format).to_string()
+    }
+
+    /// Resolve who is making a change: an explicit value, then `default_author`,
+    /// then the login name from the environment
+    pub fn actor(&self, explicit: Option<&str>) -> String {
+        explicit
+            .map(str::to_string)
+            .or_else(|| self.default_author.clone())
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .filter(|actor| !actor.trim().is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_config_uses_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = WorkspaceConfig::load(&temp_dir.path().join(CONFIG_FILE_NAME)).unwrap();
+
+        assert_eq!(config, WorkspaceConfig::default());
+        assert_eq!(config.id_prefix, "nt");
+        assert_eq!(config.default_format, "human");
+    }
+
+    #[test]
+    fn test_config_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+
+        let config = WorkspaceConfig {
+            default_author: Some("maintainer".to_string()),
+            allowed_categories: vec!["security".to_string(), "testing".to_string()],
+            id_prefix: "acme".to_string(),
+            default_format: "json".to_string(),
+            storage: StorageConfig {
+                backend: StorageBackend::Sqlite,
+                layout: JsonlLayout::PerCategory,
+            },
+            approvals: ApprovalConfig {
+                must: 2,
+                forbidden: 1,
+                ..ApprovalConfig::default()
+            },
+            signing: SigningConfig {
+                policy: SigningPolicy::Require,
+            },
+            verification: VerificationConfig {
+                timeout_secs: Some(600),
+            },
+        };
+        config.save(&path).unwrap();
+
+        let loaded = WorkspaceConfig::load(&path).unwrap();
+        assert_eq!(loaded, config);
+        assert_eq!(loaded.approvals.required(ConstraintType::Must), 2);
+        assert_eq!(loaded.approvals.required(ConstraintType::Should), 0);
+    }
+
+    #[test]
+    fn test_invalid_config_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(CONFIG_FILE_NAME);
+
+        std::fs::write(&path, "id_prefix = \"NT-1\"\n").unwrap();
+        assert!(matches!(
+            WorkspaceConfig::load(&path),
+            Err(ConstraintError::Config(_))
+        ));
+
+        std::fs::write(&path, "default_format = \"xml\"\n").unwrap();
+        assert!(matches!(
+            WorkspaceConfig::load(&path),
+            Err(ConstraintError::Config(_))
+        ));
+
+        std::fs::write(&path, "[verification]\ntimeout_secs = 0\n").unwrap();
+        assert!(matches!(
+            WorkspaceConfig::load(&path),
+            Err(ConstraintError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_category_allowed() {
+        let mut config = WorkspaceConfig::default();
+        assert!(config.is_category_allowed("anything"));
+
+        config.allowed_categories = vec!["security".to_string()];
+        assert!(config.is_category_allowed("security"));
+        assert!(!config.is_category_allowed("testing"));
+    }
+}
@@ -1,9 +1,30 @@
 //! Hash-based ID generation utilities
+//!
+//! # ID algorithm
+//!
+//! Constraint IDs are derived from content with a fixed algorithm so they are
+//! identical across platforms, compiler versions and releases:
+//!
+//! 1. Hash `TYPE \x1f category \x1f text` with SHA-256, where `TYPE` is the
+//!    uppercase RFC 2119 keyword. Alternate candidates (used when an ID is
+//!    already taken) append `\x1f` and the decimal attempt number.
+//! 2. Read the first 8 bytes of the digest as a big-endian `u64` and reduce it
+//!    modulo 36^6.
+//! 3. Write the result as 6 zero-padded base36 digits (`0-9a-z`).
+//! 4. Prepend the workspace prefix and a hyphen: `nt-ysej8l`.
 
 use sha2::{Digest, Sha256};
 
+/// Separator between hashed fields, so field boundaries cannot be forged
+const FIELD_SEPARATOR: &[u8] = b"\x1f";
+
+/// Number of distinct 6-character base36 suffixes
+const SUFFIX_SPACE: u64 = 36u64.pow(6);
+
 /// ID generation utility for constraints
-pub struct IdGenerator;
+pub struct IdGenerator {
+    prefix: String,
+}
 
 impl Default for IdGenerator {
     fn default() -> Self {
@@ -12,38 +33,91 @@ impl Default for IdGenerator {
 }
 
 impl IdGenerator {
-    /// Create a new ID generator
+    /// Prefix used for constraint IDs unless the workspace configures another one
+    pub const DEFAULT_PREFIX: &'static str = "nt";
+
+    /// Create a new ID generator using the default prefix
     pub fn new() -> Self {
-        Self
+        Self::with_prefix(Self::DEFAULT_PREFIX)
+    }
+
+    /// Create an ID generator for a workspace-specific prefix
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
     }
 
-    /// Generate a deterministic ID from constraint content
+    /// Generate the natural ID for constraint content
     #[allow(unused)]
-    pub fn generate(&mut self, text: &str, category: &str, constraint_type: &str) -> String {
+    pub fn generate(&self, text: &str, category: &str, constraint_type: &str) -> String {
+        self.generate_alternate(text, category, constraint_type, 0)
+    }
+
+    /// Generate the `attempt`-th candidate ID for constraint content
+    ///
+    /// Attempt 0 is the natural ID; later attempts salt the hash with the
+    /// attempt number.
+    pub fn generate_alternate(
+        &self,
+        text: &str,
+        category: &str,
+        constraint_type: &str,
+        attempt: u32,
+    ) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(text.as_bytes());
-        hasher.update(category.as_bytes());
         hasher.update(constraint_type.as_bytes());
+        hasher.update(FIELD_SEPARATOR);
+        hasher.update(category.as_bytes());
+        hasher.update(FIELD_SEPARATOR);
+        hasher.update(text.as_bytes());
+        if attempt > 0 {
+            hasher.update(FIELD_SEPARATOR);
+            hasher.update(attempt.to_string().as_bytes());
+        }
 
         let hash = hasher.finalize();
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&hash[..8]);
 
-        // Take first 3 bytes (24 bits) and encode as base36, ensuring exactly 6 characters
-        let suffix = Self::encode_base36_6chars(&hash[..3]);
-        format!("nt-{}", suffix)
+        let suffix = Self::encode_base36_6chars(u64::from_be_bytes(head) % SUFFIX_SPACE);
+        format!("{}-{}", self.prefix, suffix)
     }
 
-    /// Validate ID format
-    pub fn validate(id: &str) -> bool {
-        // Validate format: nt-<base36-suffix> where suffix is exactly 6 characters
-        let re = regex::Regex::new(r"^nt-[0-9a-z]{6}$").unwrap();
-        re.is_match(id)
+    /// Validate ID format against a workspace-specific prefix
+    pub fn validate_with_prefix(id: &str, prefix: &str) -> bool {
+        // Validate format: <prefix>-<base36-suffix> where suffix is exactly 6 characters
+        match id
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('-'))
+        {
+            Some(suffix) => {
+                suffix.len() == 6
+                    && suffix
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())
+            }
+            None => false,
+        }
     }
 
-    /// Convert 3 bytes to exactly 6 base36 characters with padding
-    #[allow(unused)]
-    fn encode_base36_6chars(bytes: &[u8]) -> String {
-        // Convert 3 bytes (24 bits) to u32
-        let num = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+    /// Validate ID format under any valid prefix
+    ///
+    /// Records keep the prefix they were created with, so stores accept IDs
+    /// made before the workspace's `id_prefix` was changed.
+    pub fn is_well_formed(id: &str) -> bool {
+        id.split_once('-').is_some_and(|(prefix, _)| {
+            Self::is_valid_prefix(prefix) && Self::validate_with_prefix(id, prefix)
+        })
+    }
+
+    /// Check that an ID prefix is 1-8 lowercase ASCII letters
+    pub fn is_valid_prefix(prefix: &str) -> bool {
+        (1..=8).contains(&prefix.len()) && prefix.chars().all(|c| c.is_ascii_lowercase())
+    }
+
+    /// Convert a number below 36^6 to exactly 6 base36 characters with padding
+    fn encode_base36_6chars(num: u64) -> String {
         let mut result = String::with_capacity(6);
         let chars: Vec<char> = "0123456789abcdefghijklmnopqrstuvwxyz".chars().collect();
 
@@ -64,31 +138,71 @@ mod tests {
 
     #[test]
     fn test_id_generation_deterministic() {
-        let mut generator = IdGenerator::new();
+        let generator = IdGenerator::new();
 
         let id1 = generator.generate("test text", "category", "MUST");
-        let id2 = generator.generate("test text", "category", "MUST");
+        let id2 = IdGenerator::new().generate("test text", "category", "MUST");
 
         // Different instances should generate same ID for same inputs
         assert_eq!(id1, id2);
-        assert!(IdGenerator::validate(&id1));
+        assert!(IdGenerator::validate_with_prefix(&id1, "nt"));
+    }
+
+    #[test]
+    fn test_id_algorithm_is_pinned() {
+        // These values are part of the on-disk format; changing them breaks existing IDs
+        let generator = IdGenerator::new();
+        assert_eq!(
+            generator.generate("All passwords must be hashed", "security", "MUST"),
+            "nt-ysej8l"
+        );
+        assert_eq!(
+            generator.generate_alternate("All passwords must be hashed", "security", "MUST", 1),
+            "nt-ssg0sj"
+        );
+        assert_eq!(
+            IdGenerator::with_prefix("acme").generate(
+                "All passwords must be hashed",
+                "security",
+                "MUST"
+            ),
+            "acme-ysej8l"
+        );
     }
 
     #[test]
     fn test_id_validation() {
-        assert!(IdGenerator::validate("nt-a1b2c3"));
-        assert!(IdGenerator::validate("nt-012345"));
-        assert!(IdGenerator::validate("nt-abcdef"));
-
-        assert!(!IdGenerator::validate("invalid"));
-        assert!(!IdGenerator::validate("nt-12345")); // too short
-        assert!(!IdGenerator::validate("nt-1234567")); // too long
-        assert!(!IdGenerator::validate("xx-123456")); // wrong prefix
+        assert!(IdGenerator::validate_with_prefix("nt-a1b2c3", "nt"));
+        assert!(IdGenerator::validate_with_prefix("nt-012345", "nt"));
+        assert!(IdGenerator::validate_with_prefix("nt-abcdef", "nt"));
+
+        assert!(!IdGenerator::validate_with_prefix("invalid", "nt"));
+        assert!(!IdGenerator::validate_with_prefix("nt-12345", "nt")); // too short
+        assert!(!IdGenerator::validate_with_prefix("nt-1234567", "nt")); // too long
+        assert!(!IdGenerator::validate_with_prefix("xx-123456", "nt")); // wrong prefix
+    }
+
+    #[test]
+    fn test_id_validation_with_prefix() {
+        assert!(IdGenerator::validate_with_prefix("acme-a1b2c3", "acme"));
+        assert!(!IdGenerator::validate_with_prefix("nt-a1b2c3", "acme"));
+        assert!(!IdGenerator::validate_with_prefix("acme-A1B2C3", "acme"));
+
+        assert!(IdGenerator::is_well_formed("nt-a1b2c3"));
+        assert!(IdGenerator::is_well_formed("acme-a1b2c3"));
+        assert!(!IdGenerator::is_well_formed("a1b2c3"));
+        assert!(!IdGenerator::is_well_formed("n-t-a1b2c3"));
+        assert!(!IdGenerator::is_well_formed("toolongpx-a1b2c3"));
+
+        assert!(IdGenerator::is_valid_prefix("nt"));
+        assert!(!IdGenerator::is_valid_prefix(""));
+        assert!(!IdGenerator::is_valid_prefix("Nt"));
+        assert!(!IdGenerator::is_valid_prefix("toolongpx"));
     }
 
     #[test]
     fn test_collision_handling() {
-        let mut generator = IdGenerator::new();
+        let generator = IdGenerator::new();
 
         // Generate first ID
         let id1 = generator.generate("text1", "cat", "MUST");
@@ -100,18 +214,24 @@ mod tests {
         assert_ne!(id1, id2);
 
         // Both should be valid
-        assert!(IdGenerator::validate(&id1));
-        assert!(IdGenerator::validate(&id2));
+        assert!(IdGenerator::validate_with_prefix(&id1, "nt"));
+        assert!(IdGenerator::validate_with_prefix(&id2, "nt"));
     }
 
     #[test]
     fn test_base36_encoding() {
-        let bytes = [0x12, 0x34, 0x56];
-        let encoded = IdGenerator::encode_base36_6chars(&bytes);
+        let encoded = IdGenerator::encode_base36_6chars(0x123456);
 
         assert_eq!(encoded.len(), 6);
         assert!(encoded
             .chars()
             .all(|c| c.is_alphanumeric() && c.is_lowercase() || c.is_ascii_digit()));
+
+        // The full alphabet is used, including the top of the range
+        assert_eq!(IdGenerator::encode_base36_6chars(0), "000000");
+        assert_eq!(
+            IdGenerator::encode_base36_6chars(SUFFIX_SPACE - 1),
+            "zzzzzz"
+        );
     }
 }
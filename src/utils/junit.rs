@@ -0,0 +1,208 @@
+//! JUnit XML reports, as rendered natively by CI systems such as GitLab and Jenkins
+
+use std::fmt::Write;
+
+/// A group of test cases, shown as one suite
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuite {
+    pub name: String,
+    pub cases: Vec<TestCase>,
+}
+
+/// One test case and how it ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub classname: String,
+    pub duration_ms: u64,
+    pub outcome: TestOutcome,
+    /// Captured output, attached as `system-out`
+    pub output: Option<String>,
+}
+
+/// How a test case ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome {
+    Passed,
+    /// Failed, with a one-line message; the captured output becomes the failure body
+    Failed {
+        message: String,
+        kind: String,
+    },
+    /// Not run, or run without counting, for the given reason
+    Skipped {
+        message: String,
+    },
+}
+
+impl TestSuite {
+    fn failures(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::Failed { .. }))
+    }
+
+    fn skipped(&self) -> usize {
+        self.count(|outcome| matches!(outcome, TestOutcome::Skipped { .. }))
+    }
+
+    fn duration_ms(&self) -> u64 {
+        self.cases.iter().map(|case| case.duration_ms).sum()
+    }
+
+    fn count(&self, predicate: impl Fn(&TestOutcome) -> bool) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| predicate(&case.outcome))
+            .count()
+    }
+}
+
+/// Render suites as a JUnit XML document under a `testsuites` root named `name`
+pub fn render(name: &str, suites: &[TestSuite]) -> String {
+    let total = |count: fn(&TestSuite) -> usize| suites.iter().map(count).sum::<usize>();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\">",
+        escape(name),
+        total(|suite| suite.cases.len()),
+        total(TestSuite::failures),
+        total(TestSuite::skipped),
+        seconds(suites.iter().map(TestSuite::duration_ms).sum())
+    );
+
+    for suite in suites {
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{}\">",
+            escape(&suite.name),
+            suite.cases.len(),
+            suite.failures(),
+            suite.skipped(),
+            seconds(suite.duration_ms())
+        );
+
+        for case in &suite.cases {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+                escape(&case.name),
+                escape(&case.classname),
+                seconds(case.duration_ms)
+            );
+
+            let output = case.output.as_deref().filter(|output| !output.is_empty());
+            match &case.outcome {
+                TestOutcome::Passed if output.is_none() => {
+                    xml.push_str("/>\n");
+                    continue;
+                }
+                TestOutcome::Passed => xml.push_str(">\n"),
+                TestOutcome::Failed { message, kind } => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                        escape(message),
+                        escape(kind),
+                        escape(output.unwrap_or(message))
+                    );
+                }
+                TestOutcome::Skipped { message } => {
+                    let _ = writeln!(xml, ">\n      <skipped message=\"{}\"/>", escape(message));
+                }
+            }
+            // Failures already carry the output in their body
+            let failed = matches!(case.outcome, TestOutcome::Failed { .. });
+            if let Some(output) = output.filter(|_| !failed) {
+                let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(output));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Milliseconds as the fractional seconds JUnit expects
+fn seconds(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+/// Escape text for use in XML content and attribute values
+///
+/// Control characters XML 1.0 cannot represent at all, such as the ANSI
+/// color codes test runners print, are dropped.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' || c == '\u{fffe}' || c == '\u{ffff}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_and_escaping() {
+        let suites = vec![TestSuite {
+            name: "security".to_string(),
+            cases: vec![
+                TestCase {
+                    name: "nt-aaaaaa: Hash passwords".to_string(),
+                    classname: "security".to_string(),
+                    duration_ms: 1500,
+                    outcome: TestOutcome::Passed,
+                    output: None,
+                },
+                TestCase {
+                    name: "nt-bbbbbb: Escape <html> & \"quotes\"".to_string(),
+                    classname: "security".to_string(),
+                    duration_ms: 25,
+                    outcome: TestOutcome::Failed {
+                        message: "Verification failed".to_string(),
+                        kind: "FAILED".to_string(),
+                    },
+                    output: Some("\u{1b}[31merror\u{1b}[0m: 1 < 2".to_string()),
+                },
+                TestCase {
+                    name: "nt-cccccc: Review access".to_string(),
+                    classname: "security".to_string(),
+                    duration_ms: 0,
+                    outcome: TestOutcome::Skipped {
+                        message: "No verification method specified".to_string(),
+                    },
+                    output: None,
+                },
+            ],
+        }];
+
+        let xml = render("constraint validate", &suites);
+        assert!(xml.contains(
+            r#"<testsuites name="constraint validate" tests="3" failures="1" errors="0" skipped="1" time="1.525">"#
+        ));
+        assert!(xml.contains(
+            r#"<testcase name="nt-aaaaaa: Hash passwords" classname="security" time="1.500"/>"#
+        ));
+        assert!(xml.contains(r#"name="nt-bbbbbb: Escape &lt;html&gt; &amp; &quot;quotes&quot;""#));
+        assert!(xml.contains(
+            r#"<failure message="Verification failed" type="FAILED">[31merror[0m: 1 &lt; 2</failure>"#
+        ));
+        assert!(xml.contains(r#"<skipped message="No verification method specified"/>"#));
+        assert!(!xml.contains('\u{1b}'));
+        assert!(!xml.contains("<system-out>"));
+    }
+}
@@ -0,0 +1,199 @@
+//! Local signing keys kept in `.newton/keys`
+//!
+//! The directory holds this machine's private key (`signing.key`, never
+//! committed) and the public keys trusted by the workspace, one
+//! `<key-id>.pub` file each. Both are hex encoded.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand_core::OsRng;
+
+use crate::core::constraint::Constraint;
+use crate::core::error::ConstraintError;
+use crate::core::signature::{self, RecordSignature, SignatureStatus};
+
+/// File name of the local private key
+pub const SIGNING_KEY_FILE: &str = "signing.key";
+
+/// Extension of trusted public key files
+const PUBLIC_KEY_EXTENSION: &str = "pub";
+
+/// The local signing key (if any) and the public keys trusted by the workspace
+#[derive(Debug, Default)]
+pub struct Keyring {
+    signing_key: Option<SigningKey>,
+    trusted: HashMap<String, VerifyingKey>,
+}
+
+impl Keyring {
+    /// Load the keys in `dir`; a missing directory gives an empty keyring
+    pub fn load(dir: &Path) -> Result<Self, ConstraintError> {
+        let mut keyring = Self::default();
+        if !dir.exists() {
+            return Ok(keyring);
+        }
+
+        let signing_path = dir.join(SIGNING_KEY_FILE);
+        if signing_path.exists() {
+            let bytes = read_key_file(&signing_path)?;
+            keyring.signing_key = Some(SigningKey::from_bytes(&bytes));
+        }
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(PUBLIC_KEY_EXTENSION) {
+                continue;
+            }
+
+            let key = VerifyingKey::from_bytes(&read_key_file(&path)?).map_err(|e| {
+                ConstraintError::Config(format!("Invalid public key {}: {}", path.display(), e))
+            })?;
+            keyring.trusted.insert(signature::key_id(&key), key);
+        }
+
+        Ok(keyring)
+    }
+
+    /// Generate a new local signing key in `dir` and trust its public half
+    ///
+    /// Refuses to replace an existing private key. A `.gitignore` keeps the
+    /// private key out of version control while the public key is shared.
+    pub fn generate(dir: &Path) -> Result<Self, ConstraintError> {
+        let signing_path = dir.join(SIGNING_KEY_FILE);
+        if signing_path.exists() {
+            return Err(ConstraintError::Config(format!(
+                "A signing key already exists at {}",
+                signing_path.display()
+            )));
+        }
+
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(".gitignore"), format!("{}\n", SIGNING_KEY_FILE))?;
+
+        let key = SigningKey::generate(&mut OsRng);
+        write_private_key(&signing_path, &key)?;
+        std::fs::write(
+            public_key_path(dir, &key.verifying_key()),
+            format!("{}\n", hex::encode(key.verifying_key().as_bytes())),
+        )?;
+
+        Self::load(dir)
+    }
+
+    /// The local private key, if one has been generated
+    pub fn signing_key(&self) -> Option<&SigningKey> {
+        self.signing_key.as_ref()
+    }
+
+    /// ID of the local signing key
+    pub fn signing_key_id(&self) -> Option<String> {
+        self.signing_key
+            .as_ref()
+            .map(|key| signature::key_id(&key.verifying_key()))
+    }
+
+    /// Sign a constraint with the local key
+    pub fn sign(&self, constraint: &Constraint) -> Result<RecordSignature, ConstraintError> {
+        let key = self.signing_key.as_ref().ok_or_else(|| {
+            ConstraintError::Config(
+                "No signing key found (run `constraint sign --generate-key`)".to_string(),
+            )
+        })?;
+        signature::sign(constraint, key)
+    }
+
+    /// Check a constraint's signature against the trusted keys
+    pub fn verify(&self, constraint: &Constraint) -> SignatureStatus {
+        signature::verify(constraint, &self.trusted)
+    }
+}
+
+/// Path of the trusted public key file for `key`
+pub fn public_key_path(dir: &Path, key: &VerifyingKey) -> PathBuf {
+    dir.join(format!(
+        "{}.{}",
+        signature::key_id(key),
+        PUBLIC_KEY_EXTENSION
+    ))
+}
+
+/// Read a hex-encoded 32-byte key
+fn read_key_file(path: &Path) -> Result<[u8; 32], ConstraintError> {
+    let data = std::fs::read_to_string(path)?;
+    hex::decode(data.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            ConstraintError::Config(format!(
+                "Invalid key file {}: expected 32 hex-encoded bytes",
+                path.display()
+            ))
+        })
+}
+
+/// Write the private key readable by its owner only
+fn write_private_key(path: &Path, key: &SigningKey) -> Result<(), ConstraintError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    use std::io::Write;
+    let mut file = options.open(path)?;
+    writeln!(file, "{}", hex::encode(key.to_bytes()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::constraint::{ConstraintParams, ConstraintType};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("keys");
+
+        let empty = Keyring::load(&dir).unwrap();
+        assert!(empty.signing_key().is_none());
+
+        let keyring = Keyring::generate(&dir).unwrap();
+        let key_id = keyring.signing_key_id().unwrap();
+        assert!(dir.join(format!("{}.pub", key_id)).exists());
+        assert!(Keyring::generate(&dir).is_err());
+
+        let mut constraint = Constraint::new(ConstraintParams {
+            r#type: ConstraintType::Must,
+            category: "security".to_string(),
+            text: "Hash passwords".to_string(),
+            author: "author".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        constraint.signature = Some(keyring.sign(&constraint).unwrap());
+
+        // A reloaded keyring trusts the generated key
+        let reloaded = Keyring::load(&dir).unwrap();
+        assert_eq!(
+            reloaded.verify(&constraint),
+            SignatureStatus::Valid { key_id }
+        );
+
+        // Without the public key the signature is no longer trusted
+        std::fs::remove_file(public_key_path(
+            &dir,
+            &keyring.signing_key().unwrap().verifying_key(),
+        ))
+        .unwrap();
+        assert!(matches!(
+            Keyring::load(&dir).unwrap().verify(&constraint),
+            SignatureStatus::UnknownKey { .. }
+        ));
+    }
+}
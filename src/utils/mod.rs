@@ -1,8 +1,14 @@
 //! Utility modules for the Newton Constraints CLI tool
 
+pub mod config;
 pub mod id;
+pub mod junit;
+pub mod keys;
+pub mod process;
 pub mod workspace;
 
+#[allow(unused_imports)]
+pub use config::*;
 #[allow(unused_imports)]
 pub use id::*;
 #[allow(unused_imports)]
@@ -0,0 +1,145 @@
+//! Running verification commands with a time limit
+
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How often a running command is checked against its deadline
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Output captured from a command that finished or was stopped
+#[derive(Debug)]
+pub struct CapturedOutput {
+    /// Exit status, or `None` when the command was killed at its time limit
+    pub status: Option<ExitStatus>,
+
+    /// Everything written to stdout before the command ended
+    pub stdout: String,
+
+    /// Everything written to stderr before the command ended
+    pub stderr: String,
+}
+
+impl CapturedOutput {
+    /// Whether the command was killed for running past its time limit
+    pub fn timed_out(&self) -> bool {
+        self.status.is_none()
+    }
+}
+
+/// Run a command to completion, or until `timeout` expires
+///
+/// On Unix the command leads its own process group. When it times out the
+/// whole group is killed, so processes started by a shell script do not
+/// outlive it, and when it exits any stragglers left in the group are killed
+/// so they cannot hold the output pipes open. Output written before the end
+/// is returned either way.
+pub fn run_with_timeout(
+    command: &mut Command,
+    timeout: Option<Duration>,
+) -> std::io::Result<CapturedOutput> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        match deadline {
+            Some(deadline) if Instant::now() >= deadline => break None,
+            Some(_) => std::thread::sleep(POLL_INTERVAL),
+            None => break Some(child.wait()?),
+        }
+    };
+
+    kill_group(&mut child);
+    if status.is_none() {
+        child.wait()?;
+    }
+
+    Ok(CapturedOutput {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+/// Drain a pipe on its own thread so a chatty command never blocks on a full pipe
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut data = Vec::new();
+        if let Some(mut pipe) = pipe {
+            // A read error just ends the capture; whatever arrived is kept
+            let _ = pipe.read_to_end(&mut data);
+        }
+        data
+    })
+}
+
+/// Wait for a reader thread and decode what it captured
+fn collect(reader: JoinHandle<Vec<u8>>) -> String {
+    String::from_utf8_lossy(&reader.join().unwrap_or_default()).to_string()
+}
+
+/// Kill every process in the child's process group
+#[cfg(unix)]
+fn kill_group(child: &mut Child) {
+    // The child leads its own group, so its PID is the group ID
+    let pgid = child.id() as libc::pid_t;
+    // SAFETY: kill(2) takes no pointers; a group that is already gone yields ESRCH
+    unsafe {
+        libc::kill(-pgid, libc::SIGKILL);
+    }
+}
+
+/// Kill the child (other platforms have no process groups to clean up)
+#[cfg(not(unix))]
+fn kill_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeout_kills_group_and_keeps_partial_output() {
+        let started = Instant::now();
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg("echo started; sleep 30; echo finished");
+
+        let output = run_with_timeout(&mut command, Some(Duration::from_millis(300))).unwrap();
+        assert!(output.timed_out());
+        assert_eq!(output.stdout, "started\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_finished_command_leaves_no_stragglers() {
+        let started = Instant::now();
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("sleep 30 & echo done; exit 3");
+
+        // Without the group cleanup the background sleep would hold stdout open
+        let output = run_with_timeout(&mut command, None).unwrap();
+        assert!(!output.timed_out());
+        assert_eq!(output.status.and_then(|s| s.code()), Some(3));
+        assert_eq!(output.stdout, "done\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,17 +1,44 @@
 //! Workspace discovery and management utilities
 
 use crate::core::error::ConstraintError;
+use crate::utils::config::{WorkspaceConfig, CONFIG_FILE_NAME};
 use std::path::{Path, PathBuf};
 
 /// Workspace management for constraint storage
 pub struct Workspace {
     root: PathBuf,
+    config: WorkspaceConfig,
 }
 
 impl Workspace {
-    /// Create a new workspace with the given root path
-    pub fn new(root: PathBuf) -> Self {
-        Self { root }
+    /// Open an existing workspace root, loading its configuration
+    pub fn open(root: PathBuf) -> Result<Self, ConstraintError> {
+        let config = WorkspaceConfig::load(&root.join(CONFIG_FILE_NAME))?;
+        Ok(Self { root, config })
+    }
+
+    /// Initialize a workspace in the given project directory
+    pub fn init(
+        project_dir: &Path,
+        config: WorkspaceConfig,
+        force: bool,
+    ) -> Result<Self, ConstraintError> {
+        let workspace = Self {
+            root: project_dir.join(".newton"),
+            config,
+        };
+
+        if workspace.config_path().exists() && !force {
+            return Err(ConstraintError::Config(format!(
+                "Workspace already initialized at {} (use --force to overwrite the config)",
+                workspace.root.display()
+            )));
+        }
+
+        workspace.ensure_structure()?;
+        workspace.config.save(&workspace.config_path())?;
+
+        Ok(workspace)
     }
 
     /// Discover workspace starting from current directory
@@ -30,7 +57,7 @@ impl Workspace {
             let newton_dir = current.join(".newton");
 
             if newton_dir.exists() && newton_dir.is_dir() {
-                return Ok(Self { root: newton_dir });
+                return Self::open(newton_dir);
             }
 
             // Try to go up one directory
@@ -39,17 +66,75 @@ impl Workspace {
             } else {
                 // Reached filesystem root without finding .newton
                 return Err(ConstraintError::WorkspaceNotFound(
-                    "No .newton directory found in current directory or parents".to_string(),
+                    "No .newton directory found in current directory or parents \
+                     (run `constraint init` to create one)"
+                        .to_string(),
                 ));
             }
         }
     }
 
+    /// Get the workspace configuration
+    pub fn config(&self) -> &WorkspaceConfig {
+        &self.config
+    }
+
+    /// Get the project directory holding `.newton`, where verifications run
+    pub fn project_dir(&self) -> PathBuf {
+        self.root.parent().unwrap_or(&self.root).to_path_buf()
+    }
+
+    /// Get the path of the workspace configuration file
+    pub fn config_path(&self) -> PathBuf {
+        self.root.join(CONFIG_FILE_NAME)
+    }
+
     /// Get the constraints storage directory
     pub fn constraints_dir(&self) -> PathBuf {
         self.root.join("constraints")
     }
 
+    /// Get the path of the persistent ID index
+    pub fn index_path(&self) -> PathBuf {
+        self.root.join("index.json")
+    }
+
+    /// Get the directory holding per-constraint revision history
+    pub fn history_dir(&self) -> PathBuf {
+        self.root.join("history")
+    }
+
+    /// Get the path of the alias records for retired IDs
+    pub fn aliases_path(&self) -> PathBuf {
+        self.root.join("aliases.json")
+    }
+
+    /// Get the path of the hash-chained audit log
+    pub fn audit_path(&self) -> PathBuf {
+        self.root.join("audit.jsonl")
+    }
+
+    /// Get the directory holding cached verification results
+    pub fn cache_dir(&self) -> PathBuf {
+        self.root.join("cache")
+    }
+
+    /// Get the directory holding recorded validation runs
+    pub fn runs_dir(&self) -> PathBuf {
+        self.root.join("runs")
+    }
+
+    /// Get the directory holding the local signing key and trusted public keys
+    pub fn keys_dir(&self) -> PathBuf {
+        self.root.join("keys")
+    }
+
+    /// Get the path of the SQLite constraint database
+    #[allow(unused)]
+    pub fn database_path(&self) -> PathBuf {
+        self.root.join("constraints.db")
+    }
+
     /// Get the path for a specific category directory
     pub fn category_dir(&self, category: &str) -> PathBuf {
         self.constraints_dir().join(category)
@@ -59,7 +144,7 @@ impl Workspace {
     #[allow(unused)]
     pub fn constraint_file(&self, category: &str, id: &str) -> Result<PathBuf, ConstraintError> {
         // Validate ID format
-        if !crate::utils::id::IdGenerator::validate(id) {
+        if !crate::utils::id::IdGenerator::is_well_formed(id) {
             return Err(ConstraintError::InvalidIdFormat(id.to_string()));
         }
 
@@ -155,9 +240,7 @@ mod tests {
     #[test]
     fn test_constraint_file_path() {
         let temp_dir = TempDir::new().unwrap();
-        let workspace = Workspace {
-            root: temp_dir.path().join(".newton"),
-        };
+        let workspace = Workspace::open(temp_dir.path().join(".newton")).unwrap();
 
         let file_path = workspace.constraint_file("security", "nt-a1b2c3").unwrap();
         let expected = workspace.category_dir("security").join("nt-a1b2c3.jsonl");
@@ -167,11 +250,35 @@ mod tests {
     #[test]
     fn test_invalid_id() {
         let temp_dir = TempDir::new().unwrap();
-        let workspace = Workspace {
-            root: temp_dir.path().join(".newton"),
-        };
+        let workspace = Workspace::open(temp_dir.path().join(".newton")).unwrap();
 
         let result = workspace.constraint_file("security", "invalid-id");
         assert!(matches!(result, Err(ConstraintError::InvalidIdFormat(_))));
     }
+
+    #[test]
+    fn test_workspace_init() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = WorkspaceConfig {
+            default_author: Some("maintainer".to_string()),
+            ..WorkspaceConfig::default()
+        };
+
+        let workspace = Workspace::init(temp_dir.path(), config.clone(), false).unwrap();
+        assert!(workspace.is_initialized());
+        assert!(workspace.config_path().exists());
+
+        // Re-opening the workspace reads the saved config
+        let reopened = Workspace::find_workspace_root(temp_dir.path()).unwrap();
+        assert_eq!(reopened.config(), &config);
+
+        // A second init without force is refused
+        let result = Workspace::init(temp_dir.path(), WorkspaceConfig::default(), false);
+        assert!(matches!(result, Err(ConstraintError::Config(_))));
+
+        // With force the config is replaced
+        Workspace::init(temp_dir.path(), WorkspaceConfig::default(), true).unwrap();
+        let reopened = Workspace::find_workspace_root(temp_dir.path()).unwrap();
+        assert_eq!(reopened.config(), &WorkspaceConfig::default());
+    }
 }